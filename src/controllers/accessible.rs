@@ -0,0 +1,447 @@
+use std::io::{stdin, stdout, BufRead, Write};
+
+use super::endscreen::unlock_achievements;
+use crate::models::{
+    Attack, AttackResult, Coordinates, Faction, Game, Heading, Mine, MineEffect, Placeable, Play,
+    Ratings, Records, Ship, ShotChart, Strike, Terrain, Weapon,
+};
+
+const HELP: &str = "Commands: a cell like C4 to fire a shot there, or a weapon and a cell \
+like radar C4. Weapons are shot, radar, airstrike and torpedo. \
+board describes your fleet, enemy describes your target's waters, \
+status gives scores and weapons, target red picks who to fire at, \
+and quit ends the game.";
+
+// Prints `question` on a line of its own and reads the answer. None once
+// there's nothing left to read.
+fn ask(lines: &mut impl Iterator<Item = String>, question: &str) -> Option<String> {
+    println!("{}", question);
+    print!("> ");
+    stdout().flush().unwrap();
+    lines.next().map(|line| line.trim().to_string())
+}
+
+fn parse_cell(game: &Game, name: &str) -> Option<Coordinates> {
+    Coordinates::from_name(name).filter(|cell| game.board.contains(cell))
+}
+
+fn list(cells: &[Coordinates]) -> String {
+    let names: Vec<String> = cells.iter().map(|cell| cell.name()).collect();
+    names.join(", ")
+}
+
+// "your", "enemy" or "Green Team's", for whoever owns ships in `faction`'s
+// waters.
+fn owner(game: &Game, faction: Faction) -> String {
+    if faction == Faction::Blue {
+        "your".to_string()
+    } else if game.fleets.len() == 2 {
+        "enemy".to_string()
+    } else {
+        format!("{}'s", faction.name())
+    }
+}
+
+// One sentence per shot, like "B7: hit, enemy cruiser".
+fn describe_attack(game: &Game, target: Faction, attack: &Attack) -> String {
+    let cell = attack.coordinates.name();
+    let fleet = game.fleet(target);
+    match attack.result {
+        AttackResult::Hit => {
+            let ship = fleet
+                .ships
+                .iter()
+                .find(|ship| ship.get_segment_coordinates().contains(&attack.coordinates));
+            match ship {
+                Some(ship) if fleet.is_ship_sunk(ship) => format!(
+                    "{}: hit, {} {}, sunk!",
+                    cell,
+                    owner(game, target),
+                    ship.class()
+                ),
+                Some(ship) => format!("{}: hit, {} {}", cell, owner(game, target), ship.class()),
+                None => format!("{}: hit", cell),
+            }
+        }
+        AttackResult::Miss => format!("{}: miss", cell),
+        AttackResult::Mine(MineEffect::Reveal(revealed)) => format!(
+            "{}: mine! The blast gave away a ship at {}",
+            cell,
+            revealed.name()
+        ),
+        AttackResult::Mine(MineEffect::LoseTurn) => {
+            format!("{}: mine! The next turn is lost", cell)
+        }
+    }
+}
+
+fn describe_strike(game: &Game, weapon: Weapon, target: Faction, strike: &Strike) -> String {
+    match strike {
+        Strike::Ping(ping) => format!(
+            "Radar around {}: {}",
+            ping.origin.name(),
+            if ping.found { "contact" } else { "nothing" }
+        ),
+        Strike::Attacks(attacks) if attacks.is_empty() => {
+            format!("The {} hit nothing", weapon.name().to_lowercase())
+        }
+        Strike::Attacks(attacks) => {
+            let sentences: Vec<String> = attacks
+                .iter()
+                .map(|attack| describe_attack(game, target, attack))
+                .collect();
+            sentences.join(". ")
+        }
+    }
+}
+
+fn describe_fleet(game: &Game) -> String {
+    let fleet = game.fleet(Faction::Blue);
+    let mut sentences = vec![];
+    for ship in fleet.ships.iter() {
+        let segments = ship.get_segment_coordinates();
+        let hits: Vec<Coordinates> = segments
+            .iter()
+            .copied()
+            .filter(|cell| fleet.is_hit(cell))
+            .collect();
+        let state = if fleet.is_ship_sunk(ship) {
+            ", sunk".to_string()
+        } else if hits.is_empty() {
+            "".to_string()
+        } else {
+            format!(", hit at {}", list(&hits))
+        };
+        sentences.push(format!(
+            "Your {} runs from {} to {}{}.",
+            ship.class(),
+            segments[0].name(),
+            segments[segments.len() - 1].name(),
+            state
+        ));
+    }
+    let mines: Vec<Coordinates> = fleet.mines.iter().map(|mine| mine.coordinates).collect();
+    if !mines.is_empty() {
+        sentences.push(format!("Mines at {}.", list(&mines)));
+    }
+    let misses: Vec<Coordinates> = fleet
+        .attacks
+        .iter()
+        .filter(|attack| attack.result == AttackResult::Miss)
+        .map(|attack| attack.coordinates)
+        .collect();
+    if !misses.is_empty() {
+        sentences.push(format!("Enemy shots missed at {}.", list(&misses)));
+    }
+    sentences.join(" ")
+}
+
+fn describe_waters(game: &Game, target: Faction) -> String {
+    let fleet = game.fleet(target);
+    let mut sentences = vec![format!("{}'s waters.", target.name())];
+    let cells = |result: &dyn Fn(AttackResult) -> bool| -> Vec<Coordinates> {
+        fleet
+            .attacks
+            .iter()
+            .filter(|attack| result(attack.result))
+            .map(|attack| attack.coordinates)
+            .collect()
+    };
+    let hits = cells(&|result| result == AttackResult::Hit);
+    let misses = cells(&|result| result != AttackResult::Hit);
+    sentences.push(match hits.len() {
+        0 => "No hits yet.".to_string(),
+        _ => format!("Hits at {}.", list(&hits)),
+    });
+    if !misses.is_empty() {
+        sentences.push(format!("Misses at {}.", list(&misses)));
+    }
+    for ping in fleet.pings.iter() {
+        sentences.push(format!(
+            "Radar around {} found {}.",
+            ping.origin.name(),
+            if ping.found { "a contact" } else { "nothing" }
+        ));
+    }
+    if !fleet.revealed.is_empty() {
+        sentences.push(format!(
+            "A mine gave away ships at {}.",
+            list(&fleet.revealed)
+        ));
+    }
+    sentences.join(" ")
+}
+
+fn describe_terrain(game: &Game) -> String {
+    let mut islands = vec![];
+    let mut reefs = vec![];
+    for y in 0..game.board.height {
+        for x in 0..game.board.width {
+            let cell = Coordinates { x, y };
+            match game.board.terrain_at(&cell) {
+                Terrain::Island => islands.push(cell),
+                Terrain::Reef => reefs.push(cell),
+                Terrain::Water => {}
+            }
+        }
+    }
+    let mut sentences = vec![format!(
+        "The board has rows A to {} and columns 1 to {}.",
+        (b'A' + (game.board.height - 1) as u8) as char,
+        game.board.width
+    )];
+    if !islands.is_empty() {
+        sentences.push(format!("Islands at {}.", list(&islands)));
+    }
+    if !reefs.is_empty() {
+        sentences.push(format!("Reefs at {}.", list(&reefs)));
+    }
+    sentences.join(" ")
+}
+
+fn describe_status(game: &Game) -> String {
+    let fleet = game.fleet(Faction::Blue);
+    let weapons: Vec<String> = Weapon::all()
+        .into_iter()
+        .map(|weapon| match fleet.arsenal.remaining(weapon) {
+            Some(count) => format!("{} {}", count, weapon.name().to_lowercase()),
+            None => format!("unlimited {}s", weapon.name().to_lowercase()),
+        })
+        .collect();
+    format!(
+        "{} hits and {} misses. Weapons: {}. Targeting {}.",
+        fleet.score.hits,
+        fleet.score.misses,
+        weapons.join(", "),
+        game.target.name()
+    )
+}
+
+// Asks the player where each ship and mine goes, one at a time.
+fn place_fleet(game: &mut Game, lines: &mut impl Iterator<Item = String>) -> bool {
+    for placeable in Placeable::fleet().into_iter() {
+        loop {
+            let question = match placeable {
+                Placeable::Ship(length) => format!(
+                    "Place your {}, {} cells long: type a cell and east or south, like A1 east. Or type auto.",
+                    Ship::new(Coordinates::default(), Heading::East, length).class(),
+                    length
+                ),
+                Placeable::Mine => "Lay a mine: type a cell, like C4. Or type auto.".to_string(),
+            };
+            let answer = match ask(lines, &question) {
+                Some(answer) => answer.to_lowercase(),
+                None => return false,
+            };
+            let words: Vec<&str> = answer.split_whitespace().collect();
+            if words == ["quit"] {
+                return false;
+            }
+            if words == ["help"] {
+                println!("Rows are letters and columns are numbers. Board reads out your fleet so far, quit leaves.");
+                continue;
+            }
+            if words == ["board"] {
+                println!("{}", describe_fleet(game));
+                continue;
+            }
+            if words == ["auto"] {
+                game.auto_place_fleet(&[placeable])
+                    .expect("Should have been able to place the fleet!");
+                break;
+            }
+            let cell = match words.first().and_then(|name| parse_cell(game, name)) {
+                Some(cell) => cell,
+                None => {
+                    println!("That isn't a cell on the board.");
+                    continue;
+                }
+            };
+            let placed = match placeable {
+                Placeable::Ship(length) => {
+                    let heading = match words.get(1) {
+                        None | Some(&"east") => Heading::East,
+                        Some(&"south") => Heading::South,
+                        Some(_) => {
+                            println!("Ships face east or south.");
+                            continue;
+                        }
+                    };
+                    game.place_ship(Ship::new(cell, heading, length)).is_ok()
+                }
+                Placeable::Mine => game.place_mine(Mine::new(cell)).is_ok(),
+            };
+            if placed {
+                break;
+            }
+            println!("That doesn't fit there.");
+        }
+    }
+    println!("{}", describe_fleet(game));
+    true
+}
+
+// Plays every other fleet's turn until it's the player's again, telling
+// them what happened.
+fn play_replies(game: &mut Game) {
+    loop {
+        game.next_player();
+        if game.winner().is_some() || game.fleet(Faction::Blue).is_eliminated() {
+            return;
+        }
+        let faction = game.active_player;
+        if faction == Faction::Blue {
+            if game.consume_lost_turn() {
+                println!("You sit this turn out after hitting a mine.");
+                continue;
+            }
+            return;
+        }
+        if game.auto_play_turn().is_err() {
+            continue;
+        }
+        match game.history.last().map(|turn| turn.play.clone()) {
+            Some(Play::Fire {
+                weapon,
+                target,
+                strike,
+            }) => println!(
+                "{} fires a {} at {}. {}.",
+                faction.name(),
+                weapon.name().to_lowercase(),
+                if target == Faction::Blue {
+                    "you".to_string()
+                } else {
+                    target.name().to_string()
+                },
+                describe_strike(game, weapon, target, &strike)
+            ),
+            Some(Play::Move) => println!("{} moved a ship.", faction.name()),
+            Some(Play::LostTurn) => {
+                println!(
+                    "{} sits this turn out after hitting a mine.",
+                    faction.name()
+                )
+            }
+            None => {}
+        }
+    }
+}
+
+// Plays a whole game as lines of text, for screen readers: the boards are
+// described rather than drawn, and commands are typed.
+pub fn accessible_controller(game: &mut Game) {
+    let stdin = stdin();
+    let mut lines = stdin.lock().lines().map_while(Result::ok);
+
+    println!("Rustbuckets, line mode. Type help at any prompt for commands.");
+    println!("{}", describe_terrain(game));
+
+    for faction in game.opponents().into_iter() {
+        game.active_player = faction;
+        game.place_bot_fleet()
+            .expect("Should have been able to place the fleet!");
+    }
+    game.active_player = Faction::Blue;
+
+    if !place_fleet(game, &mut lines) {
+        return;
+    }
+
+    while game.winner().is_none() && !game.fleet(Faction::Blue).is_eliminated() {
+        let answer = match ask(&mut lines, "Your turn. Where do you fire?") {
+            Some(answer) => answer.to_lowercase(),
+            None => return,
+        };
+        let words: Vec<&str> = answer.split_whitespace().collect();
+        let (weapon, cell) = match words.as_slice() {
+            [] => continue,
+            ["help"] => {
+                println!("{}", HELP);
+                continue;
+            }
+            ["quit"] => return,
+            ["board"] => {
+                println!("{}", describe_fleet(game));
+                continue;
+            }
+            ["enemy"] => {
+                println!("{}", describe_waters(game, game.target));
+                continue;
+            }
+            ["status"] => {
+                println!("{}", describe_status(game));
+                continue;
+            }
+            ["target", name] => {
+                match Faction::parse(name).map(|faction| game.select_target(faction)) {
+                    Some(Ok(_)) => println!("Targeting {}.", game.target.name()),
+                    _ => println!("There's no fleet called {} left to fire at.", name),
+                }
+                continue;
+            }
+            [cell] => (Weapon::Shot, *cell),
+            ["fire", cell] => (Weapon::Shot, *cell),
+            [weapon, cell] => match Weapon::parse(weapon) {
+                Some(weapon) => (weapon, *cell),
+                None => {
+                    println!(
+                        "There's no weapon called {}. Type help for commands.",
+                        weapon
+                    );
+                    continue;
+                }
+            },
+            _ => {
+                println!("Type help for commands.");
+                continue;
+            }
+        };
+        let cell = match parse_cell(game, cell) {
+            Some(cell) => cell,
+            None => {
+                println!("{} isn't a cell on the board.", cell.to_uppercase());
+                continue;
+            }
+        };
+        let target = game.target;
+        match game.launch(weapon, cell) {
+            Ok(strike) => println!("{}.", describe_strike(game, weapon, target, &strike)),
+            Err(message) => {
+                println!("{}.", message);
+                continue;
+            }
+        }
+        play_replies(game);
+    }
+
+    println!(
+        "{}",
+        match game.winner() {
+            Some(Faction::Blue) => "Victory! Your fleet rules the waves.".to_string(),
+            Some(faction) => format!("Defeat! {} wins.", faction.name()),
+            None => "Defeat! Your fleet has been sunk.".to_string(),
+        }
+    );
+    let recorded = Records::save(game.record());
+    if let Err(message) = recorded {
+        println!("{}; this game won't count towards your stats.", message);
+    }
+    let mut chart = ShotChart::load();
+    game.chart_shots(&mut chart);
+    let _ = chart.save();
+    let mut ratings = Ratings::load();
+    if game.rate(&mut ratings) && ratings.save().is_ok() {
+        println!(
+            "Your rating is now {:.0}.",
+            ratings.rating(&game.profile.name)
+        );
+    }
+    for achievement in unlock_achievements(game, recorded.is_ok()) {
+        println!(
+            "Achievement unlocked: {}! {}.",
+            achievement.name(),
+            achievement.description()
+        );
+    }
+}
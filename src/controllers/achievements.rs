@@ -0,0 +1,89 @@
+use std::io::{stdout, Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::{IntoRawMode, RawTerminal};
+
+use crate::{
+    controllers::{Action, Event, Events, Keymap, Mode},
+    models::{Achievement, Coordinates, Game, Label},
+    views::LabelView,
+};
+
+pub fn achievements_controller(game: &mut Game, events: &Events) {
+    let mut stdout = stdout().into_raw_mode().unwrap();
+
+    // Clear the screen
+    write!(
+        stdout,
+        "{}{}{}",
+        termion::clear::All,
+        Goto(1, 1),
+        termion::cursor::Hide
+    )
+    .unwrap();
+
+    // Models
+    let keymap = Keymap::current(game.profile.keybindings);
+    let mut lines = vec![
+        "Rustbuckets 0.1.0".to_string(),
+        format!("Press {} to go back", keymap.describe(Action::Quit)),
+        "".to_string(),
+        format!(
+            "{}'s achievements: {} of {}",
+            game.profile.name,
+            game.profile.achievements.len(),
+            Achievement::all().len()
+        ),
+        "".to_string(),
+    ];
+    for achievement in Achievement::all().into_iter() {
+        lines.push(format!(
+            "[{}] {:<14}{}",
+            if game.profile.achievements.contains(&achievement) {
+                "x"
+            } else {
+                " "
+            },
+            achievement.name(),
+            achievement.description()
+        ));
+    }
+
+    // Views
+    let views: Vec<LabelView> = lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            LabelView::new(
+                Coordinates {
+                    x: 1,
+                    y: 1 + index as u16,
+                },
+                Label::new(line),
+            )
+        })
+        .collect();
+    let render = |stdout: &mut RawTerminal<Stdout>| {
+        for view in views.iter() {
+            view.render(stdout);
+        }
+        stdout.flush().unwrap();
+    };
+    render(&mut stdout);
+
+    for event in events.inputs() {
+        let key = match event {
+            Event::Key(key) => key,
+            // Resizing can leave the old screen garbled, so draw it afresh.
+            Event::Resize => {
+                write!(stdout, "{}", termion::clear::All).unwrap();
+                render(&mut stdout);
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(Action::Quit) = keymap.action(key, &[Action::Quit]) {
+            game.switch_mode(Mode::Title);
+            break;
+        }
+    }
+}
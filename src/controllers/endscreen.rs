@@ -1,18 +1,105 @@
-use std::io::{stdin, stdout, Write};
+use std::io::{stdout, Stdout, Write};
 use termion::cursor::Goto;
-use termion::event::Key;
-use termion::input::TermRead;
-use termion::raw::IntoRawMode;
+use termion::raw::{IntoRawMode, RawTerminal};
 
 use crate::{
-    controllers::Mode,
-    models::{Coordinates, Game, Label},
-    views::LabelView,
+    controllers::{Action, Event, Events, Keymap, Mode},
+    models::{
+        Achievement, Alert, Coordinates, Faction, Game, Label, Level, Profiles, Ratings, Records,
+        ShotChart, Stats,
+    },
+    views::{
+        AlertView, AttackView, BoardView, LabelView, Layout, ShipView, StatsView, TooSmallView,
+    },
 };
 
-pub fn endscreen_controller(game: &mut Game) {
+// Draws the whole end screen: the labels up top, every fleet's results,
+// its board with nothing left hidden, and any achievements just unlocked.
+fn render_results(
+    stdout: &mut RawTerminal<Stdout>,
+    game: &Game,
+    layout: &Layout,
+    labels: &[LabelView],
+    unlocked: &[Achievement],
+) {
+    if !layout.fits_results(unlocked.len()) {
+        TooSmallView::new(
+            layout.required_for_results(unlocked.len()),
+            layout.terminal(),
+        )
+        .render(stdout);
+        stdout.flush().unwrap();
+        return;
+    }
+
+    for label in labels.iter() {
+        label.render(stdout);
+    }
+
+    for (index, fleet) in game.fleets.iter().enumerate() {
+        let stats = Stats::new(&game.history, fleet.faction);
+        StatsView::new(
+            layout.result(index),
+            game.faction_name(fleet.faction),
+            stats,
+        )
+        .render(stdout);
+
+        let origin = layout.result_board(index);
+        LabelView::new(
+            layout.result_title(index),
+            Label::new(game.faction_name(fleet.faction)),
+        )
+        .render(stdout);
+        BoardView::new(origin, game.board.clone()).render(stdout);
+        for attack in fleet.attacks.iter().copied() {
+            AttackView::new(origin, attack).render(stdout);
+        }
+        for ship in fleet.ships.iter() {
+            ShipView::new(origin, *ship)
+                .with_damage(fleet)
+                .render(stdout);
+        }
+    }
+
+    for (index, achievement) in unlocked.iter().enumerate() {
+        AlertView::new(
+            layout.unlocked(index),
+            Alert::new(
+                format!(
+                    "Achievement unlocked: {}! {}.",
+                    achievement.name(),
+                    achievement.description()
+                ),
+                Level::Success,
+            ),
+        )
+        .render(stdout);
+    }
+
+    stdout.flush().unwrap();
+}
+
+// Unlocks what the player earned in the game just finished, returning the
+// achievements that are new. They count towards the player's profile, which
+// is saved straight away so they stick even if the game is quit from here.
+// Every finished game counts, this one included even if it couldn't be
+// `recorded`.
+pub fn unlock_achievements(game: &mut Game, recorded: bool) -> Vec<Achievement> {
+    let played =
+        Records::load().for_profile(&game.profile.name).games.len() as u32 + u32::from(!recorded);
+    let earned = Achievement::evaluate(game, played);
+    let unlocked = game.profile.unlock(&earned);
+    if !unlocked.is_empty() {
+        let mut profiles = Profiles::load();
+        profiles.update(game.profile.clone());
+        let _ = profiles.save();
+    }
+    unlocked
+}
+
+pub fn endscreen_controller(game: &mut Game, events: &Events) {
     let mut stdout = stdout().into_raw_mode().unwrap();
-    let stdin = stdin();
 
     // Clear the screen
     write!(
@@ -26,28 +113,76 @@ pub fn endscreen_controller(game: &mut Game) {
 
     // Models
     let title = Label::new("Rustbuckets 0.1.0".to_string());
-    let play_instructions = Label::new("Press F to start again".to_string());
-    let quit_instructions = Label::new("Press Q to quit".to_string());
+    let keymap = Keymap::current(game.profile.keybindings);
+    let play_instructions = Label::new(format!(
+        "Press {} to start again",
+        keymap.describe(Action::Start)
+    ));
+    let quit_instructions = Label::new(format!("Press {} to quit", keymap.describe(Action::Quit)));
+    let result = Label::new(match game.winner() {
+        Some(Faction::Blue) => "Victory! Your fleet rules the waves.".to_string(),
+        Some(faction) => format!("Defeat! {} wins.", faction.name()),
+        None => "Defeat! Your fleet has been sunk.".to_string(),
+    });
+    let recorded = Records::save(game.record());
+    let saved = Label::new(match recorded {
+        Ok(_) => "".to_string(),
+        Err(message) => format!("{}; this game won't count towards your stats.", message),
+    });
+    let mut chart = ShotChart::load();
+    game.chart_shots(&mut chart);
+    let _ = chart.save();
+    let mut ratings = Ratings::load();
+    let before = ratings.rating(&game.profile.name);
+    let rating = Label::new(if !game.rate(&mut ratings) {
+        format!("Rating: {:.0} (unchanged)", before)
+    } else if let Err(message) = ratings.save() {
+        format!("Rating: {:.0} ({})", before, message)
+    } else {
+        let after = ratings.rating(&game.profile.name);
+        format!(
+            "Rating: {:.0} -> {:.0} ({:+.0})",
+            before,
+            after,
+            after - before
+        )
+    });
 
-    // Views
-    let title_view = LabelView::new(Coordinates { x: 1, y: 1 }, title);
-    let play_instructions_view = LabelView::new(Coordinates { x: 1, y: 2 }, play_instructions);
-    let quit_instructions_view = LabelView::new(Coordinates { x: 1, y: 3 }, quit_instructions);
+    let unlocked = unlock_achievements(game, recorded.is_ok());
 
-    // Initial render
-    title_view.render(&mut stdout);
-    play_instructions_view.render(&mut stdout);
-    quit_instructions_view.render(&mut stdout);
+    // Views, placed to suit the terminal
+    let label_views = |layout: &Layout| {
+        [
+            LabelView::new(Coordinates { x: 1, y: 1 }, title.clone()),
+            LabelView::new(Coordinates { x: 1, y: 2 }, play_instructions.clone()),
+            LabelView::new(Coordinates { x: 1, y: 3 }, quit_instructions.clone()),
+            LabelView::new(layout.outcome(), result.clone()),
+            LabelView::new(layout.unsaved(), saved.clone()),
+            LabelView::new(layout.rating(), rating.clone()),
+        ]
+    };
+    let mut layout = Layout::new(&game.board, game.fleets.len() - 1);
+    let mut labels = label_views(&layout);
+    render_results(&mut stdout, game, &layout, &labels, &unlocked);
 
-    stdout.flush().unwrap();
-
-    for c in stdin.keys() {
-        match c.unwrap() {
-            Key::Char('f') => {
+    for event in events.inputs() {
+        let key = match event {
+            Event::Key(key) => key,
+            Event::Resize => {
+                layout = Layout::new(&game.board, game.fleets.len() - 1);
+                labels = label_views(&layout);
+                write!(stdout, "{}", termion::clear::All).unwrap();
+                render_results(&mut stdout, game, &layout, &labels, &unlocked);
+                continue;
+            }
+            _ => continue,
+        };
+        match keymap.action(key, &[Action::Start, Action::Quit]) {
+            Some(Action::Start) => {
                 game.switch_mode(Mode::Setup);
                 break;
             }
-            Key::Char('q') => {
+            Some(Action::Quit) => {
                 game.switch_mode(Mode::Exit);
                 break;
             }
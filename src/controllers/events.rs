@@ -0,0 +1,102 @@
+use std::io::stdin;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::thread;
+use std::time::Duration;
+use termion::event::{self, Key, MouseEvent};
+use termion::input::TermRead;
+
+use crate::controllers::Action;
+
+pub enum Event {
+    Key(Key),
+    Mouse(MouseEvent),
+    // A key the keymap has given a meaning, as worked out by a screen.
+    Action(Action),
+    // The terminal changed size, so the screen needs laying out again.
+    Resize,
+    // Nothing was pressed for a whole tick.
+    Tick,
+}
+
+// Set from the SIGWINCH handler, which can't do much more than this.
+static RESIZED: AtomicBool = AtomicBool::new(false);
+
+extern "C" fn on_resize(_: libc::c_int) {
+    RESIZED.store(true, Ordering::SeqCst);
+}
+
+// Keypresses are read on their own thread so controllers can wait for
+// input and still get on with things, like running down a clock, while
+// the player thinks.
+pub struct Events {
+    inputs: Receiver<event::Event>,
+    tick: Duration,
+}
+
+impl Events {
+    pub fn new(tick: Duration) -> Events {
+        let (sender, inputs) = channel();
+        thread::spawn(move || {
+            for input in stdin().events() {
+                let input = match input {
+                    Ok(input) => input,
+                    Err(_) => break,
+                };
+                if sender.send(input).is_err() {
+                    break;
+                }
+            }
+        });
+        unsafe {
+            let handler: extern "C" fn(libc::c_int) = on_resize;
+            libc::signal(libc::SIGWINCH, handler as libc::sighandler_t);
+        }
+        Events { inputs, tick }
+    }
+
+    // Waits for the next key or click, or a tick if none comes in time.
+    // A resize is noticed within a tick. Returns None once stdin is closed.
+    pub fn next(&self) -> Option<Event> {
+        loop {
+            if RESIZED.swap(false, Ordering::SeqCst) {
+                return Some(Event::Resize);
+            }
+            match self.inputs.recv_timeout(self.tick) {
+                Ok(input) => match Events::convert(input) {
+                    Some(event) => return Some(event),
+                    None => continue,
+                },
+                Err(RecvTimeoutError::Timeout) => return Some(Event::Tick),
+                Err(RecvTimeoutError::Disconnected) => return None,
+            }
+        }
+    }
+
+    // Keys, clicks and resizes, for screens that have no use for ticks.
+    pub fn inputs(&self) -> impl Iterator<Item = Event> + '_ {
+        std::iter::from_fn(move || loop {
+            match self.next()? {
+                Event::Tick => continue,
+                event => return Some(event),
+            }
+        })
+    }
+
+    // Blocks on keypresses alone, for screens that only need a keyboard.
+    pub fn keys(&self) -> impl Iterator<Item = Key> + '_ {
+        self.inputs().filter_map(|event| match event {
+            Event::Key(key) => Some(key),
+            _ => None,
+        })
+    }
+
+    // Anything termion doesn't recognise is dropped.
+    fn convert(input: event::Event) -> Option<Event> {
+        match input {
+            event::Event::Key(key) => Some(Event::Key(key)),
+            event::Event::Mouse(mouse) => Some(Event::Mouse(mouse)),
+            event::Event::Unsupported(_) => None,
+        }
+    }
+}
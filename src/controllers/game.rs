@@ -1,21 +1,411 @@
-use std::io::{stdin, stdout, Write};
+use std::io::{stdout, Stdout, Write};
+use std::time::{Duration, Instant};
 use termion::cursor::Goto;
-use termion::event::Key;
-use termion::input::TermRead;
-use termion::raw::IntoRawMode;
+use termion::event::{Key, MouseButton, MouseEvent};
+use termion::raw::{IntoRawMode, RawTerminal};
 
 use crate::{
-    controllers::Mode,
+    controllers::{Action, Event, Events, Keymap, Mode},
     models::{
-        Alert, Attack, AttackResult, Board, Coordinates, Cursor, Game, Heading, Label, Level,
-        Scores, Ship,
+        Alert, Attack, AttackResult, Clock, Coordinates, Cursor, Direction, Faction, Game, Label,
+        Level, MineEffect, Strike, Weapon,
+    },
+    views::{
+        cell_at, AlertView, ArsenalView, AttackView, BoardView, ClockView, CursorView,
+        IncomingView, LabelView, Layout, MineView, PingView, RevealView, ScoresView, ShipView,
+        TooSmallView,
     },
-    views::{AlertView, AttackView, BoardView, CursorView, LabelView, ScoresView, ShipView},
 };
 
-pub fn game_controller(game: &mut Game) {
+fn describe_blue_attack(attack: Attack) -> Alert {
+    match attack.result {
+        AttackResult::Hit => Alert::new("That was a hit!".to_string(), Level::Success),
+        AttackResult::Miss => Alert::new("You missed!".to_string(), Level::Warning),
+        AttackResult::Mine(MineEffect::Reveal(_)) => Alert::new(
+            "You hit a mine! The blast gave away one of your ships!".to_string(),
+            Level::Error,
+        ),
+        AttackResult::Mine(MineEffect::LoseTurn) => Alert::new(
+            "You hit a mine! You'll lose your next turn!".to_string(),
+            Level::Error,
+        ),
+    }
+}
+
+fn describe_enemy_attack(faction: Faction, attack: Attack) -> Alert {
+    let name = faction.name();
+    match attack.result {
+        AttackResult::Hit => Alert::new(format!("{} hit a ship!", name), Level::Warning),
+        AttackResult::Miss => Alert::new(format!("{} missed!", name), Level::Info),
+        AttackResult::Mine(MineEffect::Reveal(_)) => Alert::new(
+            format!("{} hit your mine and gave away a ship!", name),
+            Level::Success,
+        ),
+        AttackResult::Mine(MineEffect::LoseTurn) => Alert::new(
+            format!("{} hit your mine and lose their next turn!", name),
+            Level::Success,
+        ),
+    }
+}
+
+fn describe_blue_strike(weapon: Weapon, strike: Strike) -> Alert {
+    let name = weapon.name().to_lowercase();
+    match strike {
+        Strike::Ping(ping) if ping.found => {
+            Alert::new("Radar contact in that area!".to_string(), Level::Success)
+        }
+        Strike::Ping(_) => Alert::new("Radar shows nothing there.".to_string(), Level::Info),
+        Strike::Attacks(attacks) => {
+            let hits = attacks
+                .iter()
+                .filter(|attack| attack.result == AttackResult::Hit)
+                .count();
+            let mine = attacks
+                .iter()
+                .find(|attack| matches!(attack.result, AttackResult::Mine(_)));
+            match attacks.len() {
+                0 => Alert::new(format!("Your {} found nothing!", name), Level::Warning),
+                1 => describe_blue_attack(attacks[0]),
+                _ if mine.is_some() => describe_blue_attack(*mine.unwrap()),
+                _ if hits > 0 => Alert::new(
+                    format!("Your {} scored {} hit(s)!", name, hits),
+                    Level::Success,
+                ),
+                _ => Alert::new(format!("Your {} hit nothing!", name), Level::Warning),
+            }
+        }
+    }
+}
+
+fn describe_enemy_strike(faction: Faction, weapon: Weapon, strike: Strike) -> Alert {
+    let name = weapon.name().to_lowercase();
+    match strike {
+        Strike::Ping(_) => Alert::new(
+            format!("{} swept your waters with radar!", faction.name()),
+            Level::Warning,
+        ),
+        Strike::Attacks(attacks) => {
+            let hits = attacks
+                .iter()
+                .filter(|attack| attack.result == AttackResult::Hit)
+                .count();
+            let mine = attacks
+                .iter()
+                .find(|attack| matches!(attack.result, AttackResult::Mine(_)));
+            match attacks.len() {
+                0 => Alert::new(
+                    format!("{}'s {} found nothing!", faction.name(), name),
+                    Level::Info,
+                ),
+                1 => describe_enemy_attack(faction, attacks[0]),
+                _ if mine.is_some() => describe_enemy_attack(faction, *mine.unwrap()),
+                _ if hits > 0 => Alert::new(
+                    format!("{}'s {} scored {} hit(s)!", faction.name(), name, hits),
+                    Level::Warning,
+                ),
+                _ => Alert::new(
+                    format!("{}'s {} hit nothing!", faction.name(), name),
+                    Level::Info,
+                ),
+            }
+        }
+    }
+}
+
+// A strike between two AI fleets; the player only sees the gist of it.
+fn describe_rival_strike(faction: Faction, target: Faction, strike: Strike) -> Alert {
+    match strike {
+        Strike::Attacks(attacks) if attacks.iter().any(|a| a.result == AttackResult::Hit) => {
+            Alert::new(
+                format!("{} scored a hit on {}!", faction.name(), target.name()),
+                Level::Info,
+            )
+        }
+        _ => Alert::new(
+            format!("{} fired on {}.", faction.name(), target.name()),
+            Level::Info,
+        ),
+    }
+}
+
+// Where the AI fleets are in replying to the player's turn. Each step
+// waits out the game's AI delay so the player can follow along.
+#[derive(Copy, Clone)]
+enum Reply {
+    // The next fleet acts once the pause is up.
+    Waiting(Instant),
+    // A fleet has fired and the strike lands once the pause is up.
+    Incoming {
+        weapon: Weapon,
+        coordinates: Coordinates,
+        fired: Instant,
+    },
+}
+
+// Whether `key` goes towards typing a cell name rather than an action.
+// Capital letters start one unless a keys file has bound them to
+// something, and digits carry one on.
+fn types_cell(keymap: &Keymap, key: Key, typing: bool) -> bool {
+    match key {
+        Key::Char(c) if c.is_ascii_uppercase() => keymap.action(key, &PLAY_ACTIONS).is_none(),
+        Key::Char(c) => typing && c.is_ascii_digit(),
+        _ => false,
+    }
+}
+
+// What the player hears when an AI fleet has nothing left worth firing.
+fn hold_fire(faction: Faction) -> Alert {
+    Alert::new(format!("{} holds its fire.", faction.name()), Level::Info)
+}
+
+// Fires a planned strike for the active fleet, if there is one and it's
+// still possible.
+fn try_launch(game: &mut Game, plan: Option<(Weapon, Coordinates)>) -> Option<(Weapon, Strike)> {
+    let (weapon, coordinates) = plan?;
+    game.launch(weapon, coordinates)
+        .ok()
+        .map(|strike| (weapon, strike))
+}
+
+// Resolves a strike the active AI fleet committed to. If it turns out not
+// to be possible after all, the fleet plans again, then settles for a plain
+// shot wherever it can, and failing that holds its fire.
+fn land_strike(game: &mut Game, weapon: Weapon, coordinates: Coordinates) -> Alert {
+    let faction = game.active_player;
+    let target = game.target;
+    let landed = try_launch(game, Some((weapon, coordinates)))
+        .or_else(|| {
+            let plan = game.auto_plan_strike().ok();
+            try_launch(game, plan)
+        })
+        .or_else(|| {
+            let plan = game
+                .random_attack()
+                .ok()
+                .map(|coords| (Weapon::Shot, coords));
+            try_launch(game, plan)
+        });
+    match landed {
+        Some((weapon, strike)) if target == Faction::Blue => {
+            describe_enemy_strike(faction, weapon, strike)
+        }
+        Some((_, strike)) => describe_rival_strike(faction, target, strike),
+        None => hold_fire(faction),
+    }
+}
+
+// Hands the turn to the next fleet and starts its move. Returns None once
+// it's back to the player, along with anything worth telling them.
+fn begin_turn(game: &mut Game, player_target: Faction) -> (Option<Reply>, Option<Alert>) {
+    let pause = Reply::Waiting(Instant::now() + game.ai_delay);
+    game.next_player();
+    if game.winner().is_some() || game.fleet(Faction::Blue).is_eliminated() {
+        return (None, None);
+    }
+
+    let faction = game.active_player;
+    if faction == Faction::Blue {
+        // A mine may have cost us this turn, in which case the AIs go
+        // again.
+        if game.consume_lost_turn() {
+            return (
+                Some(pause),
+                Some(Alert::new(
+                    "You sit this turn out after hitting a mine!".to_string(),
+                    Level::Warning,
+                )),
+            );
+        }
+        // Keep the player aimed where they were, if that fleet is still
+        // afloat.
+        let _ = game.select_target(player_target);
+        return (None, None);
+    }
+
+    if game.consume_lost_turn() {
+        return (
+            Some(pause),
+            Some(Alert::new(
+                format!("{} is still reeling from a mine!", faction.name()),
+                Level::Info,
+            )),
+        );
+    }
+    // An engine picks its own target along with its strike.
+    let (weapon, coordinates) = match game.engine_plan_strike() {
+        Some(plan) => plan,
+        None => {
+            // A ship that turns out to be boxed in stays put, and the fleet
+            // fires instead.
+            let moved = game
+                .auto_plan_move()
+                .is_some_and(|(index, direction)| game.move_ship(index, direction).is_ok());
+            if moved {
+                return (
+                    Some(pause),
+                    Some(Alert::new(
+                        format!(
+                            "{} moved a ship! Misses may be out of date.",
+                            faction.name()
+                        ),
+                        Level::Warning,
+                    )),
+                );
+            }
+            let target = game.auto_plan_target();
+            game.select_target(target).unwrap();
+            match game.auto_plan_strike() {
+                Ok(plan) => plan,
+                Err(_) => return (Some(pause), Some(hold_fire(faction))),
+            }
+        }
+    };
+    let target = game.target;
+    let alert = if target == Faction::Blue {
+        Alert::new(
+            format!(
+                "Incoming {} from {}!",
+                weapon.name().to_lowercase(),
+                faction.name()
+            ),
+            Level::Warning,
+        )
+    } else {
+        Alert::new(
+            format!("{} is firing on {}...", faction.name(), target.name()),
+            Level::Info,
+        )
+    };
+    (
+        Some(Reply::Incoming {
+            weapon,
+            coordinates,
+            fired: Instant::now(),
+        }),
+        Some(alert),
+    )
+}
+
+// Moves the AI reply along if its current pause is up.
+fn advance_reply(
+    game: &mut Game,
+    reply: Reply,
+    player_target: Faction,
+) -> (Option<Reply>, Option<Alert>) {
+    match reply {
+        Reply::Waiting(until) if Instant::now() < until => (Some(reply), None),
+        Reply::Waiting(_) => begin_turn(game, player_target),
+        Reply::Incoming { fired, .. } if fired.elapsed() < game.ai_delay => (Some(reply), None),
+        Reply::Incoming {
+            weapon,
+            coordinates,
+            ..
+        } => {
+            let alert = land_strike(game, weapon, coordinates);
+            (
+                Some(Reply::Waiting(Instant::now() + game.ai_delay)),
+                Some(alert),
+            )
+        }
+    }
+}
+
+// An opponent's board title, marking the current target and any fleet
+// that has gone down.
+fn opponent_title(game: &Game, faction: Faction, target: Faction) -> Label {
+    let marker = if game.fleet(faction).is_eliminated() {
+        " (sunk)"
+    } else if target == faction {
+        " <- target"
+    } else {
+        ""
+    };
+    Label::new(format!("{:<24}", format!("{}{}", faction.name(), marker)))
+}
+
+// Draws every opponent's board along with what's known about it.
+fn render_opponents(
+    stdout: &mut RawTerminal<Stdout>,
+    game: &Game,
+    layout: &Layout,
+    opponents: &[Faction],
+    target: Faction,
+) {
+    for (index, faction) in opponents.iter().enumerate() {
+        let fleet = game.fleet(*faction);
+        let origin = layout.opponent_board(index);
+        LabelView::new(
+            layout.opponent_title(index),
+            opponent_title(game, *faction, target),
+        )
+        .render(stdout);
+        BoardView::new(origin, game.board.clone()).render(stdout);
+        ScoresView::new(layout.opponent_scores(index), fleet.damage()).render(stdout);
+        for ping in fleet.pings.iter() {
+            PingView::new(origin, *ping, &game.board).render(stdout);
+        }
+        for coords in fleet.revealed.iter() {
+            RevealView::new(origin, *coords).render(stdout);
+        }
+        for attack in fleet.attacks.iter().copied() {
+            AttackView::new(origin, attack).render(stdout);
+        }
+        // Once a fleet is out of the game there's nothing left to hide.
+        if fleet.is_eliminated() {
+            for ship in fleet.ships.iter() {
+                ShipView::new(origin, *ship)
+                    .with_damage(fleet)
+                    .render(stdout);
+            }
+        }
+        if let Some(attack) = fleet.attacks.last() {
+            AttackView::new(origin, *attack).latest().render(stdout);
+        }
+    }
+}
+
+// Where the attack cursor goes: over whichever board is being targeted.
+fn target_board_origin(game: &Game, layout: &Layout, opponents: &[Faction]) -> Coordinates {
+    let index = opponents
+        .iter()
+        .position(|faction| *faction == game.target)
+        .unwrap_or(0);
+    layout.opponent_board(index)
+}
+
+// Everything that can be done with a key during a game.
+const PLAY_ACTIONS: [Action; 13] = [
+    Action::Quit,
+    Action::NextTarget,
+    Action::MoveShip,
+    Action::NextShip,
+    Action::MoveUp,
+    Action::MoveDown,
+    Action::MoveLeft,
+    Action::MoveRight,
+    Action::Shot,
+    Action::Radar,
+    Action::Airstrike,
+    Action::Torpedo,
+    Action::Fire,
+];
+
+// The opponent and cell under a click, if it landed on one of their
+// boards.
+fn clicked_cell(
+    game: &Game,
+    layout: &Layout,
+    opponents: &[Faction],
+    x: u16,
+    y: u16,
+) -> Option<(Faction, Coordinates)> {
+    opponents.iter().enumerate().find_map(|(index, faction)| {
+        cell_at(layout.opponent_board(index), &game.board, x, y).map(|cell| (*faction, cell))
+    })
+}
+
+pub fn game_controller(game: &mut Game, events: &Events) {
     let mut stdout = stdout().into_raw_mode().unwrap();
-    let stdin = stdin();
 
     write!(
         stdout,
@@ -26,156 +416,332 @@ pub fn game_controller(game: &mut Game) {
     )
     .unwrap();
 
+    game.started = Instant::now();
+
     // Models
     let title = Label::new("Rustbuckets 0.1.0".to_string());
-    let red_board_title = Label::new("Red Team".to_string());
-    let blue_board_title = Label::new("Blue Team".to_string());
-    let red_board = Board::new(game.width, game.height);
-    let blue_board = Board::new(game.width, game.height);
+    let opponents: Vec<Faction> = game
+        .fleets
+        .iter()
+        .map(|fleet| fleet.faction)
+        .filter(|faction| *faction != Faction::Blue)
+        .collect();
+    let mut layout = Layout::new(&game.board, opponents.len());
+    let blue_board_title = Label::new(game.faction_name(Faction::Blue));
     let mut cursor = Cursor::default();
-    let blue_instructions = Alert::new("It's time to fight!".to_string(), Level::Info);
+    let mut weapon = Weapon::default();
+    let mut moving: Option<usize> = None;
+    // A cell name being typed, like B7.
+    let mut typed: Option<String> = None;
+    let keymap = Keymap::current(game.profile.keybindings);
+    let blue_instructions = Alert::new(
+        if opponents.len() > 1 {
+            format!(
+                "It's time to fight! Press {} to pick a target.",
+                keymap.describe(Action::NextTarget)
+            )
+        } else {
+            "It's time to fight!".to_string()
+        },
+        Level::Info,
+    );
 
     // Views
     let title_view = LabelView::new(Coordinates { x: 1, y: 1 }, title);
-    let red_board_title_view = LabelView::new(Coordinates { x: 1, y: 3 }, red_board_title);
-    let red_board_view = BoardView::new(Coordinates { x: 1, y: 4 }, red_board);
-    let mut blue_instructions_view = AlertView::new(Coordinates { x: 1, y: 23 }, blue_instructions);
-    let blue_board_title_view = LabelView::new(Coordinates { x: 1, y: 27 }, blue_board_title);
-    let blue_board_view = BoardView::new(Coordinates { x: 1, y: 28 }, blue_board);
-    let mut blue_ship_views: Vec<ShipView> = vec![];
-    let mut red_team_score_view = ScoresView::new(Coordinates { x: 36, y: 0 }, game.blue_score);
-    let mut blue_team_score_view = ScoresView::new(Coordinates { x: 36, y: 24 }, game.red_score);
-    for ship in game.blue_ships.iter() {
-        blue_ship_views.push(ShipView::new(
-            Coordinates {
-                x: blue_board_view.origin.x,
-                y: blue_board_view.origin.y,
-            },
-            ship.clone(),
-        ))
-    }
-    let mut cursor_view = CursorView::new(red_board_view.origin, cursor);
+    let mut blue_instructions_view = AlertView::new(layout.alert(), blue_instructions);
+    let mut blue_board_title_view = LabelView::new(layout.player_title(), blue_board_title);
+    let mut blue_board_view = BoardView::new(layout.player_board(), game.board.clone());
+    let mut blue_team_score_view =
+        ScoresView::new(layout.player_scores(), game.fleet(Faction::Blue).damage());
+    let mut arsenal_view =
+        ArsenalView::new(layout.arsenal(), game.fleet(Faction::Blue).arsenal, weapon);
+    let mut cursor_view = CursorView::new(target_board_origin(game, &layout, &opponents), cursor);
+    let mut reply: Option<Reply> = None;
+    let mut player_target = game.target;
+    let mut clock = game.turn_timer.map(Clock::new);
+    let mut clock_view = ClockView::new(
+        layout.clock(),
+        clock.unwrap_or_else(|| Clock::new(Duration::from_secs(0))),
+    );
 
     // Initial render
-    title_view.render(&mut stdout);
-    red_board_title_view.render(&mut stdout);
-    red_board_view.render(&mut stdout);
-    blue_board_title_view.render(&mut stdout);
-    blue_board_view.render(&mut stdout);
-    red_team_score_view.render(&mut stdout);
-    blue_team_score_view.render(&mut stdout);
-    blue_instructions_view.render(&mut stdout);
-    for ship_view in blue_ship_views.iter() {
-        ship_view.render(&mut stdout);
-    }
-    cursor_view.render(&mut stdout);
+    if layout.fits() {
+        title_view.render(&mut stdout);
+        render_opponents(&mut stdout, game, &layout, &opponents, game.target);
+        blue_board_title_view.render(&mut stdout);
+        blue_board_view.render(&mut stdout);
+        blue_team_score_view.render(&mut stdout);
+        arsenal_view.render(&mut stdout);
+        if clock.is_some() {
+            clock_view.render(&mut stdout);
+        }
+        blue_instructions_view.render(&mut stdout);
+        for ship in game.fleet(Faction::Blue).ships.iter() {
+            ShipView::new(blue_board_view.origin, *ship).render(&mut stdout);
+        }
+        for mine in game.fleet(Faction::Blue).mines.iter() {
+            MineView::new(blue_board_view.origin, *mine).render(&mut stdout);
+        }
+        cursor_view.render(&mut stdout);
+    } else {
+        TooSmallView::new(layout.required(), layout.terminal()).render(&mut stdout);
+    }
 
     stdout.flush().unwrap();
 
-    for c in stdin.keys() {
-        match c.unwrap() {
-            Key::Char('q') => {
-                game.switch_mode(Mode::Title);
-                break;
+    while let Some(event) = events.next() {
+        let event = match event {
+            Event::Key(key) if types_cell(&keymap, key, typed.is_some()) => event,
+            Event::Key(key) => match keymap.action(key, &PLAY_ACTIONS) {
+                Some(action) => Event::Action(action),
+                None => continue,
+            },
+            // Clicking a cell on an opponent's board aims there and fires.
+            Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y))
+                if reply.is_none() && moving.is_none() =>
+            {
+                match clicked_cell(game, &layout, &opponents, x, y) {
+                    Some((faction, cell)) if game.select_target(faction).is_ok() => {
+                        cursor.origin = cell;
+                        cursor_view =
+                            CursorView::new(target_board_origin(game, &layout, &opponents), cursor);
+                        Event::Action(Action::Fire)
+                    }
+                    _ => continue,
+                }
             }
-            Key::Char('w') => {
-                if cursor.origin.y > 0 {
-                    cursor = cursor.move_up();
-                    cursor_view = cursor_view.update(cursor);
+            Event::Mouse(_) => continue,
+            event => event,
+        };
+        // Only more digits carry on with a cell name.
+        match event {
+            Event::Key(Key::Char('0'..='9')) | Event::Tick | Event::Resize => {}
+            _ => typed = None,
+        }
+        match event {
+            // Everything moves to suit the new size, so start from a blank
+            // screen.
+            Event::Resize => {
+                layout = Layout::new(&game.board, opponents.len());
+                write!(stdout, "{}", termion::clear::All).unwrap();
+                blue_instructions_view.origin = layout.alert();
+                blue_board_title_view = LabelView::new(
+                    layout.player_title(),
+                    Label::new(game.faction_name(Faction::Blue)),
+                );
+                blue_board_view.origin = layout.player_board();
+                blue_team_score_view =
+                    ScoresView::new(layout.player_scores(), game.fleet(Faction::Blue).damage());
+                arsenal_view =
+                    ArsenalView::new(layout.arsenal(), game.fleet(Faction::Blue).arsenal, weapon);
+                clock_view = ClockView::new(
+                    layout.clock(),
+                    clock.unwrap_or_else(|| Clock::new(Duration::from_secs(0))),
+                );
+                cursor_view =
+                    CursorView::new(target_board_origin(game, &layout, &opponents), cursor);
+            }
+            Event::Tick if reply.is_some() => {
+                let (next, alert) = advance_reply(game, reply.unwrap(), player_target);
+                if let Some(alert) = alert {
+                    blue_instructions_view = blue_instructions_view.update(alert);
+                }
+                // Back to the player, with a fresh clock.
+                if next.is_none() {
+                    clock = clock.map(Clock::reset);
                 }
+                reply = next;
             }
-            Key::Char('a') => {
-                if cursor.origin.x > 0 {
-                    cursor = cursor.move_left();
-                    cursor_view = cursor_view.update(cursor);
+            Event::Tick => match clock {
+                // Out of time: a shot goes off wherever it may.
+                Some(turn_clock) if turn_clock.is_expired() => {
+                    moving = None;
+                    let coords = game.random_attack().unwrap();
+                    let strike = game.launch(Weapon::Shot, coords).unwrap();
+                    blue_instructions_view = blue_instructions_view.update(Alert::new(
+                        format!(
+                            "Time's up! {}",
+                            describe_blue_strike(Weapon::Shot, strike).content
+                        ),
+                        Level::Warning,
+                    ));
+                    weapon = Weapon::Shot;
+                    player_target = game.target;
+                    reply = Some(Reply::Waiting(Instant::now() + game.ai_delay));
                 }
+                Some(turn_clock) => {
+                    clock_view = clock_view.update(turn_clock);
+                    if layout.fits() {
+                        clock_view.render(&mut stdout);
+                        stdout.flush().unwrap();
+                    }
+                    continue;
+                }
+                None => continue,
+            },
+            Event::Action(Action::Quit) => {
+                game.switch_mode(Mode::Title);
+                break;
+            }
+            // Hold on until the other fleets have made their moves.
+            Event::Key(_) | Event::Action(_) if reply.is_some() => continue,
+            Event::Action(Action::NextTarget) => {
+                let living = game.opponents();
+                let current = living.iter().position(|faction| *faction == game.target);
+                let next = match current {
+                    Some(position) => living[(position + 1) % living.len()],
+                    None => living[0],
+                };
+                game.select_target(next).unwrap();
+                cursor_view =
+                    CursorView::new(target_board_origin(game, &layout, &opponents), cursor);
+                blue_instructions_view = blue_instructions_view.update(Alert::new(
+                    format!("Targeting {}!", next.name()),
+                    Level::Info,
+                ));
+            }
+            Event::Action(Action::MoveShip) if !game.moving_fleet => {
+                blue_instructions_view = blue_instructions_view.update(Alert::new(
+                    "Ships can't move in this game!".to_string(),
+                    Level::Warning,
+                ));
             }
-            Key::Char('s') => {
-                if cursor.origin.y < game.height - 1 {
-                    cursor = cursor.move_down();
-                    cursor_view = cursor_view.update(cursor);
+            Event::Action(Action::MoveShip) => {
+                moving = match moving {
+                    Some(_) => None,
+                    None => game.movable_ships().first().copied(),
+                };
+                blue_instructions_view = blue_instructions_view.update(match moving {
+                    Some(_) => Alert::new(
+                        format!(
+                            "Move with {}, {} for the next ship, {} to cancel",
+                            keymap.describe_movement(),
+                            keymap.describe(Action::NextShip),
+                            keymap.describe(Action::MoveShip)
+                        ),
+                        Level::Info,
+                    ),
+                    None if game.movable_ships().is_empty() => {
+                        Alert::new("None of your ships can move!".to_string(), Level::Warning)
+                    }
+                    None => Alert::new("Select a cell to attack!".to_string(), Level::Info),
+                });
+            }
+            Event::Action(Action::NextShip) if moving.is_some() => {
+                let movable = game.movable_ships();
+                let current = movable.iter().position(|index| Some(*index) == moving);
+                moving = match current {
+                    Some(position) => movable.get((position + 1) % movable.len()).copied(),
+                    None => movable.first().copied(),
+                };
+            }
+            Event::Action(
+                action @ (Action::MoveUp | Action::MoveLeft | Action::MoveDown | Action::MoveRight),
+            ) if moving.is_some() => {
+                let direction = match action {
+                    Action::MoveUp => Direction::Up,
+                    Action::MoveLeft => Direction::Left,
+                    Action::MoveDown => Direction::Down,
+                    _ => Direction::Right,
+                };
+                match game.move_ship(moving.unwrap(), direction) {
+                    Ok(_) => {
+                        moving = None;
+                        player_target = game.target;
+                        reply = Some(Reply::Waiting(Instant::now() + game.ai_delay));
+                    }
+                    Err(message) => {
+                        blue_instructions_view = blue_instructions_view
+                            .update(Alert::new(format!("{}!", message), Level::Warning));
+                    }
                 }
             }
-            Key::Char('d') => {
-                if cursor.origin.x < game.width - 1 {
-                    cursor = cursor.move_right();
-                    cursor_view = cursor_view.update(cursor);
+            Event::Action(Action::MoveUp) if cursor.origin.y > 0 => {
+                cursor = cursor.move_up();
+                cursor_view = cursor_view.update(cursor);
+            }
+            Event::Action(Action::MoveLeft) if cursor.origin.x > 0 => {
+                cursor = cursor.move_left();
+                cursor_view = cursor_view.update(cursor);
+            }
+            Event::Action(Action::MoveDown) if cursor.origin.y < game.board.height - 1 => {
+                cursor = cursor.move_down();
+                cursor_view = cursor_view.update(cursor);
+            }
+            Event::Action(Action::MoveRight) if cursor.origin.x < game.board.width - 1 => {
+                cursor = cursor.move_right();
+                cursor_view = cursor_view.update(cursor);
+            }
+            // A capital letter starts typing a cell name and digits finish
+            // it. The cursor jumps there as soon as it names a cell.
+            Event::Key(Key::Char(c)) if moving.is_none() => {
+                let mut name = match typed.take() {
+                    Some(name) if c.is_ascii_digit() => name,
+                    _ => String::new(),
+                };
+                name.push(c);
+                let alert = match Coordinates::from_name(&name) {
+                    Some(cell) if cell.x < game.board.width && cell.y < game.board.height => {
+                        cursor.origin = cell;
+                        cursor_view = cursor_view.update(cursor);
+                        Alert::new(
+                            format!(
+                                "Aiming at {}. Press {} to fire!",
+                                name,
+                                keymap.describe(Action::Fire)
+                            ),
+                            Level::Info,
+                        )
+                    }
+                    None if name.len() == 1 => {
+                        Alert::new(format!("Row {}: now type the column.", name), Level::Info)
+                    }
+                    _ => Alert::new(format!("There's no cell {}!", name), Level::Warning),
+                };
+                blue_instructions_view = blue_instructions_view.update(alert);
+                typed = Some(name);
+            }
+            Event::Action(
+                action @ (Action::Shot | Action::Radar | Action::Airstrike | Action::Torpedo),
+            ) => {
+                let selected = match action {
+                    Action::Shot => Weapon::Shot,
+                    Action::Radar => Weapon::Radar,
+                    Action::Airstrike => Weapon::Airstrike,
+                    _ => Weapon::Torpedo,
+                };
+                if game.fleet(Faction::Blue).arsenal.has(selected) {
+                    weapon = selected;
+                } else {
+                    blue_instructions_view = blue_instructions_view.update(Alert::new(
+                        format!("You have no {} left!", selected.name().to_lowercase()),
+                        Level::Warning,
+                    ));
                 }
             }
-            Key::Char('f') => {
-                match game.place_attack(cursor.origin) {
-                    Ok(attack) => {
-                        match attack.result {
-                            AttackResult::Hit => {
-                                blue_instructions_view = blue_instructions_view.update(Alert::new(
-                                    "That was a hit!".to_string(),
+            Event::Action(Action::Fire) if moving.is_none() => {
+                let target = game.target;
+                match game.launch(weapon, cursor.origin) {
+                    Ok(strike) => {
+                        blue_instructions_view =
+                            blue_instructions_view.update(if game.fleet(target).is_eliminated() {
+                                Alert::new(
+                                    format!("You sank the last of {}!", target.name()),
                                     Level::Success,
-                                ));
-                            }
-                            AttackResult::Miss => {
-                                blue_instructions_view = blue_instructions_view
-                                    .update(Alert::new("You missed!".to_string(), Level::Warning));
-                            }
-                        }
-                        blue_instructions_view.render(&mut stdout);
-                        stdout.flush().unwrap();
+                                )
+                            } else {
+                                describe_blue_strike(weapon, strike)
+                            });
+                        weapon = Weapon::Shot;
 
                         // Attack placed.  Now it's time for the
                         // AI to retaliate.
-
-                        game.toggle_active_player();
-
-                        loop {
-                            let ai_attack_coords = game.auto_plan_attack().unwrap();
-
-                            match game.place_attack(ai_attack_coords) {
-                                Ok(attack) => {
-                                    match attack.result {
-                                        AttackResult::Hit => {
-                                            blue_instructions_view =
-                                                blue_instructions_view.update(Alert::new(
-                                                    "They hit a ship!".to_string(),
-                                                    Level::Warning,
-                                                ));
-                                        }
-                                        AttackResult::Miss => {
-                                            blue_instructions_view = blue_instructions_view.update(
-                                                Alert::new("They missed!".to_string(), Level::Info),
-                                            );
-                                        }
-                                    }
-
-                                    blue_instructions_view.render(&mut stdout);
-                                    stdout.flush().unwrap();
-
-                                    break;
-                                }
-                                Err(_) => {
-                                    // handle err?
-                                    blue_instructions_view = blue_instructions_view.update(
-                                        Alert::new("The AI is confused!".to_string(), Level::Error),
-                                    );
-                                    blue_instructions_view.render(&mut stdout);
-                                    stdout.flush().unwrap();
-                                }
-                            }
-                        }
-
-                        blue_instructions_view = blue_instructions_view.update(Alert::new(
-                            "Select a cell to attack!".to_string(),
-                            Level::Info,
-                        ));
-                        blue_instructions_view.render(&mut stdout);
-                        stdout.flush().unwrap();
-
-                        game.toggle_active_player();
+                        player_target = game.target;
+                        reply = Some(Reply::Waiting(Instant::now() + game.ai_delay));
                     }
-                    Err(_) => {
-                        // handle err
-                        blue_instructions_view = blue_instructions_view.update(Alert::new(
-                            "An attack can't be made there!".to_string(),
-                            Level::Warning,
-                        ));
+                    Err(message) => {
+                        blue_instructions_view = blue_instructions_view
+                            .update(Alert::new(format!("{}!", message), Level::Warning));
                         blue_instructions_view.render(&mut stdout);
                         stdout.flush().unwrap();
                     }
@@ -185,38 +751,119 @@ pub fn game_controller(game: &mut Game) {
         }
 
         // Was there a win?
-        if game.blue_score.hits >= 16 || game.red_score.hits >= 16 {
+        if game.winner().is_some() || game.fleet(Faction::Blue).is_eliminated() {
             game.switch_mode(Mode::Endscreen);
             break;
         }
 
         // Update score views
-        red_team_score_view = red_team_score_view.update(game.blue_score);
-        blue_team_score_view = blue_team_score_view.update(game.red_score);
+        blue_team_score_view = blue_team_score_view.update(game.fleet(Faction::Blue).damage());
+        arsenal_view = arsenal_view.update(game.fleet(Faction::Blue).arsenal, weapon);
+
+        if !layout.fits() {
+            TooSmallView::new(layout.required(), layout.terminal()).render(&mut stdout);
+            stdout.flush().unwrap();
+            continue;
+        }
 
         // Rerender
+        let blue_fleet = game.fleet(Faction::Blue);
         title_view.render(&mut stdout);
-        red_board_title_view.render(&mut stdout);
-        red_board_view.render(&mut stdout);
+        // While the AI fleets take their turns, keep showing the player's
+        // own choice of target.
+        let target = match reply {
+            Some(_) => player_target,
+            None => game.target,
+        };
+        render_opponents(&mut stdout, game, &layout, &opponents, target);
         blue_board_title_view.render(&mut stdout);
         blue_board_view.render(&mut stdout);
-        red_team_score_view.render(&mut stdout);
         blue_team_score_view.render(&mut stdout);
+        arsenal_view.render(&mut stdout);
+        if let Some(turn_clock) = clock {
+            clock_view = clock_view.update(turn_clock);
+            clock_view.render(&mut stdout);
+        }
         blue_instructions_view.render(&mut stdout);
-        for ship_view in blue_ship_views.iter() {
-            ship_view.render(&mut stdout);
+        for ping in blue_fleet.pings.iter() {
+            PingView::new(blue_board_view.origin, *ping, &game.board).render(&mut stdout);
+        }
+        for mine in blue_fleet.mines.iter() {
+            MineView::new(blue_board_view.origin, *mine).render(&mut stdout);
         }
         // Populate attack views list with attacks and render.
-        for attack in game.blue_attacks.clone().into_iter() {
+        for attack in blue_fleet.attacks.iter().copied() {
             let attack_view = AttackView::new(blue_board_view.origin, attack);
             attack_view.render(&mut stdout);
         }
-        for attack in game.red_attacks.clone().into_iter() {
-            let attack_view = AttackView::new(red_board_view.origin, attack);
-            attack_view.render(&mut stdout);
+        // Ships go over the shots so hits show as damage.
+        for ship in blue_fleet.ships.iter() {
+            ShipView::new(blue_board_view.origin, *ship)
+                .with_damage(blue_fleet)
+                .render(&mut stdout);
+        }
+        if let Some(attack) = blue_fleet.attacks.last() {
+            AttackView::new(blue_board_view.origin, *attack)
+                .latest()
+                .render(&mut stdout);
+        }
+        match moving {
+            Some(index) => CursorView::new(
+                blue_board_view.origin,
+                Cursor {
+                    origin: blue_fleet.ships[index].origin,
+                },
+            )
+            .render(&mut stdout),
+            None => cursor_view.render(&mut stdout),
+        }
+        if let Some(Reply::Incoming {
+            coordinates, fired, ..
+        }) = reply
+        {
+            if game.target == Faction::Blue {
+                let frame = fired.elapsed().as_millis() as usize / 150;
+                IncomingView::new(blue_board_view.origin, coordinates, frame).render(&mut stdout);
+            }
         }
-        cursor_view.render(&mut stdout);
 
         stdout.flush().unwrap();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Attack, Board, Keybindings};
+
+    #[test]
+    fn test_bound_capitals_are_actions_not_cells() {
+        let keymap = Keymap::from_config(Keybindings::Wasd, "quit = Q").unwrap();
+        assert!(!types_cell(&keymap, Key::Char('Q'), false));
+        assert!(types_cell(&keymap, Key::Char('B'), false));
+        assert!(types_cell(&keymap, Key::Char('7'), true));
+        // Digits on their own still pick weapons.
+        assert!(!types_cell(&keymap, Key::Char('1'), false));
+    }
+
+    #[test]
+    fn test_ai_holds_fire_with_nothing_left_to_hit() {
+        let mut game = Game {
+            board: Board::new(2, 1),
+            active_player: Faction::Red,
+            target: Faction::Blue,
+            ..Default::default()
+        };
+        for x in 0..2 {
+            game.fleet_mut(Faction::Blue)
+                .attacks
+                .push(Attack::with_result(
+                    Coordinates { x, y: 0 },
+                    AttackResult::Miss,
+                ));
+        }
+        let alert = land_strike(&mut game, Weapon::Shot, Coordinates { x: 0, y: 0 });
+        assert_eq!(alert.content, "Red Team holds its fire.");
+        assert!(game.history.is_empty());
+    }
+}
@@ -0,0 +1,313 @@
+use std::fs;
+use termion::event::Key;
+
+use crate::models::{Keybindings, Records};
+
+// Everything a player can ask for, whichever keys they press to do it.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Action {
+    MoveUp,
+    MoveDown,
+    MoveLeft,
+    MoveRight,
+    Fire,
+    Rotate,
+    Quit,
+    Start,
+    Stats,
+    Achievements,
+    Profiles,
+    Difficulty,
+    NextTarget,
+    MoveShip,
+    NextShip,
+    Shot,
+    Radar,
+    Airstrike,
+    Torpedo,
+}
+
+impl Action {
+    pub fn all() -> Vec<Action> {
+        vec![
+            Action::MoveUp,
+            Action::MoveDown,
+            Action::MoveLeft,
+            Action::MoveRight,
+            Action::Fire,
+            Action::Rotate,
+            Action::Quit,
+            Action::Start,
+            Action::Stats,
+            Action::Achievements,
+            Action::Profiles,
+            Action::Difficulty,
+            Action::NextTarget,
+            Action::MoveShip,
+            Action::NextShip,
+            Action::Shot,
+            Action::Radar,
+            Action::Airstrike,
+            Action::Torpedo,
+        ]
+    }
+
+    // What the action is called in the keys file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Action::MoveUp => "move_up",
+            Action::MoveDown => "move_down",
+            Action::MoveLeft => "move_left",
+            Action::MoveRight => "move_right",
+            Action::Fire => "fire",
+            Action::Rotate => "rotate",
+            Action::Quit => "quit",
+            Action::Start => "start",
+            Action::Stats => "stats",
+            Action::Achievements => "achievements",
+            Action::Profiles => "profiles",
+            Action::Difficulty => "difficulty",
+            Action::NextTarget => "next_target",
+            Action::MoveShip => "move_ship",
+            Action::NextShip => "next_ship",
+            Action::Shot => "shot",
+            Action::Radar => "radar",
+            Action::Airstrike => "airstrike",
+            Action::Torpedo => "torpedo",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Action> {
+        Action::all()
+            .into_iter()
+            .find(|action| action.name() == name)
+    }
+}
+
+// Keys with a name of their own in the keys file; anything else is
+// written as the character itself.
+const KEY_NAMES: [(&str, Key); 8] = [
+    ("space", Key::Char(' ')),
+    ("enter", Key::Char('\n')),
+    ("tab", Key::Char('\t')),
+    ("backspace", Key::Backspace),
+    ("up", Key::Up),
+    ("down", Key::Down),
+    ("left", Key::Left),
+    ("right", Key::Right),
+];
+
+fn parse_key(name: &str) -> Option<Key> {
+    if let Some((_, key)) = KEY_NAMES
+        .iter()
+        .find(|(known, _)| known.eq_ignore_ascii_case(name))
+    {
+        return Some(*key);
+    }
+    let mut chars = name.chars();
+    match (chars.next(), chars.next()) {
+        (Some(c), None) => Some(Key::Char(c)),
+        _ => None,
+    }
+}
+
+// How a key is shown in instructions, like "Press F to fire".
+fn key_name(key: Key) -> String {
+    match KEY_NAMES.iter().find(|(_, known)| *known == key) {
+        Some((name, _)) => {
+            let mut chars = name.chars();
+            chars.next().unwrap().to_uppercase().chain(chars).collect()
+        }
+        None => match key {
+            Key::Char(c) => c.to_uppercase().to_string(),
+            key => format!("{:?}", key),
+        },
+    }
+}
+
+// Which keys do what. A key can stand for different actions on different
+// screens, like S for stats on the title screen and for moving down in a
+// game, so each screen asks only about the actions it understands.
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: Vec<(Action, Key)>,
+}
+
+impl Keymap {
+    pub fn preset(keybindings: Keybindings) -> Keymap {
+        let movement = match keybindings {
+            Keybindings::Wasd => ['w', 's', 'a', 'd'].map(Key::Char),
+            Keybindings::Arrows => [Key::Up, Key::Down, Key::Left, Key::Right],
+            Keybindings::Vim => ['k', 'j', 'h', 'l'].map(Key::Char),
+        };
+        let mut bindings = vec![
+            (Action::MoveUp, movement[0]),
+            (Action::MoveDown, movement[1]),
+            (Action::MoveLeft, movement[2]),
+            (Action::MoveRight, movement[3]),
+        ];
+        bindings.extend(
+            [
+                (Action::Fire, 'f'),
+                (Action::Fire, '\n'),
+                (Action::Rotate, 'r'),
+                (Action::Quit, 'q'),
+                (Action::Start, 'f'),
+                (Action::Stats, 's'),
+                (Action::Achievements, 'a'),
+                (Action::Profiles, 'p'),
+                (Action::Difficulty, 'd'),
+                (Action::NextTarget, 't'),
+                (Action::NextTarget, '\t'),
+                (Action::MoveShip, 'm'),
+                (Action::NextShip, 'n'),
+                (Action::Shot, '1'),
+                (Action::Radar, '2'),
+                (Action::Airstrike, '3'),
+                (Action::Torpedo, '4'),
+            ]
+            .iter()
+            .map(|(action, c)| (*action, Key::Char(*c))),
+        );
+        Keymap { bindings }
+    }
+
+    // Reads `action = key, key` lines over the top of a preset. Any
+    // action named in the file loses its preset keys. Blank lines and
+    // lines starting with # are skipped.
+    pub fn from_config(keybindings: Keybindings, config: &str) -> Result<Keymap, String> {
+        let mut keymap = Keymap::preset(keybindings);
+        for (number, line) in config.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (name, keys) = line
+                .split_once('=')
+                .ok_or_else(|| format!("Line {} of the keys file has no '='", number + 1))?;
+            let action = Action::parse(name.trim())
+                .ok_or_else(|| format!("Unknown action '{}' in the keys file", name.trim()))?;
+            let keys = keys
+                .split(',')
+                .map(|key| {
+                    parse_key(key.trim())
+                        .ok_or_else(|| format!("Unknown key '{}' in the keys file", key.trim()))
+                })
+                .collect::<Result<Vec<Key>, String>>()?;
+            keymap.bindings.retain(|(bound, _)| *bound != action);
+            keymap
+                .bindings
+                .extend(keys.into_iter().map(|key| (action, key)));
+        }
+        Ok(keymap)
+    }
+
+    // The keys file lives with the rest of the player's data, as `keys`.
+    // Without one, the preset is used as it is.
+    pub fn load(keybindings: Keybindings) -> Result<Keymap, String> {
+        match Records::data_dir().map(|dir| fs::read_to_string(dir.join("keys"))) {
+            Some(Ok(config)) => Keymap::from_config(keybindings, &config),
+            _ => Ok(Keymap::preset(keybindings)),
+        }
+    }
+
+    // The player's keymap, or just the preset if the keys file is broken.
+    // That's reported when the game starts, so there's no need to again.
+    pub fn current(keybindings: Keybindings) -> Keymap {
+        Keymap::load(keybindings).unwrap_or_else(|_| Keymap::preset(keybindings))
+    }
+
+    // The first of `actions` that `key` is bound to.
+    pub fn action(&self, key: Key, actions: &[Action]) -> Option<Action> {
+        self.bindings
+            .iter()
+            .find(|(action, bound)| *bound == key && actions.contains(action))
+            .map(|(action, _)| *action)
+    }
+
+    pub fn keys(&self, action: Action) -> Vec<Key> {
+        self.bindings
+            .iter()
+            .filter(|(bound, _)| *bound == action)
+            .map(|(_, key)| *key)
+            .collect()
+    }
+
+    // The main key for `action`, for instructions.
+    pub fn describe(&self, action: Action) -> String {
+        match self.keys(action).first() {
+            Some(key) => key_name(*key),
+            None => "(unbound)".to_string(),
+        }
+    }
+
+    // The four movement keys together, like "WASD" or "Up/Left/Down/Right".
+    pub fn describe_movement(&self) -> String {
+        let names: Vec<String> = [
+            Action::MoveUp,
+            Action::MoveLeft,
+            Action::MoveDown,
+            Action::MoveRight,
+        ]
+        .iter()
+        .map(|action| self.describe(*action))
+        .collect();
+        if names.iter().all(|name| name.chars().count() == 1) {
+            names.concat()
+        } else {
+            names.join("/")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_presets_move_with_their_own_keys() {
+        let movement = [Action::MoveUp, Action::MoveLeft];
+        let vim = Keymap::preset(Keybindings::Vim);
+        assert_eq!(vim.action(Key::Char('k'), &movement), Some(Action::MoveUp));
+        assert_eq!(vim.action(Key::Char('w'), &movement), None);
+        let arrows = Keymap::preset(Keybindings::Arrows);
+        assert_eq!(arrows.action(Key::Left, &movement), Some(Action::MoveLeft));
+        assert_eq!(arrows.describe_movement(), "Up/Left/Down/Right");
+        assert_eq!(
+            Keymap::preset(Keybindings::Wasd).describe_movement(),
+            "WASD"
+        );
+    }
+
+    #[test]
+    fn test_screens_only_see_their_own_actions() {
+        let keymap = Keymap::preset(Keybindings::Wasd);
+        let s = Key::Char('s');
+        assert_eq!(keymap.action(s, &[Action::Stats]), Some(Action::Stats));
+        assert_eq!(
+            keymap.action(s, &[Action::MoveDown]),
+            Some(Action::MoveDown)
+        );
+        assert_eq!(keymap.action(s, &[Action::Fire]), None);
+    }
+
+    #[test]
+    fn test_config_replaces_the_preset_keys() {
+        let config = "# My keys\n\nfire = space, x\nquit=Q\n";
+        let keymap = Keymap::from_config(Keybindings::Wasd, config).unwrap();
+        assert_eq!(
+            keymap.keys(Action::Fire),
+            vec![Key::Char(' '), Key::Char('x')]
+        );
+        assert_eq!(keymap.describe(Action::Fire), "Space");
+        assert_eq!(keymap.keys(Action::Quit), vec![Key::Char('Q')]);
+        assert_eq!(keymap.keys(Action::Rotate), vec![Key::Char('r')]);
+    }
+
+    #[test]
+    fn test_config_errors() {
+        assert!(Keymap::from_config(Keybindings::Wasd, "fire").is_err());
+        assert!(Keymap::from_config(Keybindings::Wasd, "jump = j").is_err());
+        assert!(Keymap::from_config(Keybindings::Wasd, "fire = ctrl-f").is_err());
+    }
+}
@@ -1,10 +1,24 @@
+mod accessible;
+mod achievements;
 mod endscreen;
+mod events;
 mod game;
+mod keymap;
 mod mode;
+mod profiles;
+mod protocol;
 mod setup;
+mod simulate;
+mod stats;
+mod terminal;
 mod title;
+mod tournament;
 
 pub use self::{
-    endscreen::endscreen_controller, game::game_controller, mode::Mode, setup::setup_controller,
-    title::title_controller,
+    accessible::accessible_controller, achievements::achievements_controller,
+    endscreen::endscreen_controller, events::Event, events::Events, game::game_controller,
+    keymap::Action, keymap::Keymap, mode::Mode, profiles::profiles_controller,
+    protocol::protocol_controller, setup::setup_controller, simulate::simulate_controller,
+    stats::stats_controller, terminal::Terminal, title::title_controller,
+    tournament::tournament_controller,
 };
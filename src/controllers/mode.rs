@@ -1,14 +1,12 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq)]
 pub enum Mode {
+    #[default]
     Title,
     Setup,
     Play,
     Endscreen,
+    Stats,
+    Profiles,
+    Achievements,
     Exit,
 }
-
-impl Default for Mode {
-    fn default() -> Self {
-        Mode::Title
-    }
-}
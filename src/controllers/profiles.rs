@@ -0,0 +1,165 @@
+use std::io::{stdout, Stdout, Write};
+use termion::cursor::Goto;
+use termion::event::Key;
+use termion::raw::{IntoRawMode, RawTerminal};
+
+use crate::{
+    controllers::{Events, Mode},
+    models::{Coordinates, Game, Label, Profile, Profiles},
+    views::LabelView,
+};
+
+// Long enough for most names, short enough for the leaderboard column.
+const MAX_NAME_LENGTH: usize = 20;
+
+fn render_profiles(
+    stdout: &mut RawTerminal<Stdout>,
+    profiles: &Profiles,
+    selected: usize,
+    naming: &Option<String>,
+    message: &str,
+) {
+    write!(stdout, "{}", termion::clear::All).unwrap();
+
+    let mut lines = vec![
+        "Rustbuckets 0.1.0".to_string(),
+        "Press F to play as the highlighted profile, Q to go back".to_string(),
+        "W/S to choose, N for a new profile".to_string(),
+        "K, B, D and C change keybindings, board size, difficulty and colours".to_string(),
+        "".to_string(),
+    ];
+    for (index, profile) in profiles.entries.iter().enumerate() {
+        lines.push(format!(
+            "{} {:<22}Keys: {:<8}Board: {:<7}Difficulty: {:<8}Theme: {}",
+            if index == selected { ">" } else { " " },
+            profile.name,
+            profile.keybindings.name(),
+            format!("{}x{}", profile.board_width, profile.board_height),
+            profile.difficulty.name(),
+            profile.theme.name()
+        ));
+    }
+    lines.push("".to_string());
+    if let Some(name) = naming {
+        lines.push(format!(
+            "New profile name: {}_   (Enter to create, Esc to cancel)",
+            name
+        ));
+    }
+    lines.push(message.to_string());
+
+    for (index, line) in lines.into_iter().enumerate() {
+        LabelView::new(
+            Coordinates {
+                x: 1,
+                y: 1 + index as u16,
+            },
+            Label::new(line),
+        )
+        .render(stdout);
+    }
+    stdout.flush().unwrap();
+}
+
+// Describes how saving went, for the line under the list.
+fn saved(profiles: &Profiles) -> String {
+    match profiles.save() {
+        Ok(_) => "".to_string(),
+        Err(message) => format!("{}; changes will be lost on exit.", message),
+    }
+}
+
+pub fn profiles_controller(game: &mut Game, events: &Events) {
+    let mut stdout = stdout().into_raw_mode().unwrap();
+
+    write!(stdout, "{}{}", Goto(1, 1), termion::cursor::Hide).unwrap();
+
+    // Models
+    let mut profiles = Profiles::load();
+    // Whoever is playing now shows up even if they've never been saved.
+    if profiles.find(&game.profile.name).is_none() {
+        profiles.update(game.profile.clone());
+    }
+    let mut selected = profiles
+        .entries
+        .iter()
+        .position(|profile| profile.name == game.profile.name)
+        .unwrap_or(0);
+    let mut naming: Option<String> = None;
+    let mut message = String::new();
+
+    render_profiles(&mut stdout, &profiles, selected, &naming, &message);
+
+    for key in events.keys() {
+        match naming.take() {
+            Some(name) => match key {
+                Key::Char('\n') if Profile::is_valid_name(&name) => {
+                    let name = name.trim();
+                    if profiles.find(name).is_none() {
+                        profiles.update(Profile::new(name));
+                    }
+                    selected = profiles
+                        .entries
+                        .iter()
+                        .position(|profile| profile.name == name)
+                        .unwrap();
+                    message = saved(&profiles);
+                }
+                Key::Esc => {}
+                Key::Backspace => {
+                    let mut name = name;
+                    name.pop();
+                    naming = Some(name);
+                }
+                Key::Char(c) if !c.is_control() && name.chars().count() < MAX_NAME_LENGTH => {
+                    naming = Some(format!("{}{}", name, c));
+                }
+                _ => naming = Some(name),
+            },
+            None => match key {
+                Key::Char('w') | Key::Up if selected > 0 => selected -= 1,
+                Key::Char('s') | Key::Down if selected + 1 < profiles.entries.len() => {
+                    selected += 1
+                }
+                Key::Char('n') => naming = Some(String::new()),
+                Key::Char('k') => {
+                    let profile = &mut profiles.entries[selected];
+                    profile.keybindings = profile.keybindings.next();
+                    message = saved(&profiles);
+                }
+                Key::Char('b') => {
+                    profiles.entries[selected].next_board_size();
+                    message = saved(&profiles);
+                }
+                Key::Char('d') => {
+                    let profile = &mut profiles.entries[selected];
+                    profile.difficulty = profile.difficulty.next();
+                    message = saved(&profiles);
+                }
+                Key::Char('c') => {
+                    let profile = &mut profiles.entries[selected];
+                    profile.theme = profile.theme.next();
+                    message = saved(&profiles);
+                }
+                Key::Char('f') | Key::Char('\n') => {
+                    let profile = profiles.entries[selected].clone();
+                    profiles.select(profile.clone());
+                    // Nothing to report if it can't be saved; the choice
+                    // still holds until the game exits.
+                    let _ = profiles.save();
+                    game.difficulty = profile.difficulty;
+                    game.profile = profile;
+                    game.switch_mode(Mode::Title);
+                    break;
+                }
+                Key::Char('q') => {
+                    game.switch_mode(Mode::Title);
+                    break;
+                }
+                _ => {}
+            },
+        }
+
+        render_profiles(&mut stdout, &profiles, selected, &naming, &message);
+    }
+}
@@ -0,0 +1,122 @@
+use std::io::{stdin, BufRead};
+
+use crate::models::{report, Command, Coordinates, Faction, Game, Placeable, Terrain};
+
+// Prints whatever happened on the latest turn.
+fn report_last(game: &Game) {
+    if let Some(turn) = game.history.last() {
+        for line in report(game, turn) {
+            println!("{}", line);
+        }
+    }
+}
+
+fn is_over(game: &Game) -> bool {
+    game.winner().is_some() || game.fleet(Faction::Blue).is_eliminated()
+}
+
+// Plays every other fleet's turn until it's the player's again.
+fn play_replies(game: &mut Game) {
+    loop {
+        game.next_player();
+        if is_over(game) {
+            return;
+        }
+        let faction = game.active_player;
+        if faction == Faction::Blue {
+            if game.consume_lost_turn() {
+                report_last(game);
+                continue;
+            }
+            return;
+        }
+        if game.auto_play_turn().is_ok() {
+            report_last(game);
+        }
+    }
+}
+
+// Plays a whole game over stdin and stdout, one command and one or more
+// plain result lines at a time, so scripts can drive it without a
+// terminal.
+pub fn protocol_controller(game: &mut Game) {
+    println!("board {} {}", game.board.width, game.board.height);
+    for y in 0..game.board.height {
+        for x in 0..game.board.width {
+            let cell = Coordinates { x, y };
+            match game.board.terrain_at(&cell) {
+                Terrain::Island => println!("island {}", cell.name()),
+                Terrain::Reef => println!("reef {}", cell.name()),
+                Terrain::Water => {}
+            }
+        }
+    }
+
+    for faction in game.opponents().into_iter() {
+        game.active_player = faction;
+        game.place_bot_fleet()
+            .expect("Should have been able to place the fleet!");
+    }
+    game.active_player = Faction::Blue;
+
+    // Whatever the player has yet to place.
+    let mut unplaced = Placeable::fleet();
+
+    let stdin = stdin();
+    for line in stdin.lock().lines().map_while(Result::ok) {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let command = match Command::parse(&game.board, &line) {
+            Ok(command) => command,
+            Err(message) => {
+                println!("error {}", message);
+                continue;
+            }
+        };
+        match command {
+            Command::Quit => return,
+            command @ Command::Place(..) => {
+                match game.place_commands(vec![command], &mut unplaced) {
+                    Ok(_) => println!("ok"),
+                    Err(message) => println!("error {}", message),
+                }
+            }
+            Command::Auto => {
+                if game.auto_place_fleet(&unplaced).is_err() {
+                    println!("error no room left");
+                    continue;
+                }
+                unplaced.clear();
+                println!("ok");
+            }
+            Command::Target(faction) => match game.select_target(faction) {
+                Ok(_) => println!("ok"),
+                Err(_) => println!("error can't target that fleet"),
+            },
+            Command::Fire(weapon, cell) => {
+                if !unplaced.is_empty() {
+                    println!("error fleet not placed");
+                    continue;
+                }
+                match game.launch(weapon, cell) {
+                    Ok(_) => report_last(game),
+                    Err(message) => {
+                        println!("error {}", message.to_lowercase());
+                        continue;
+                    }
+                }
+                play_replies(game);
+            }
+        }
+        if is_over(game) {
+            break;
+        }
+    }
+
+    if let Some(winner) = game.winner() {
+        println!("winner {}", winner.short_name());
+    } else if game.fleet(Faction::Blue).is_eliminated() {
+        println!("eliminated blue");
+    }
+}
@@ -1,18 +1,68 @@
-use std::io::{stdin, stdout, Write};
+use std::io::{stdout, Write};
 use termion::cursor::Goto;
-use termion::event::Key;
-use termion::input::TermRead;
+use termion::event::{MouseButton, MouseEvent};
 use termion::raw::IntoRawMode;
 
 use crate::{
-    controllers::Mode,
-    models::{Alert, Board, Coordinates, Game, Heading, Label, Level, Ship},
-    views::{AlertView, BoardView, LabelView, ScoresView, ShipView},
+    controllers::{Action, Event, Events, Keymap, Mode},
+    models::{Alert, Coordinates, Faction, Game, Heading, Label, Level, Mine, Placeable, Ship},
+    views::{
+        cell_at, AlertView, BoardView, LabelView, Layout, MineView, ScoresView, ShipView,
+        TooSmallView,
+    },
 };
 
-pub fn setup_controller(game: &mut Game) {
+// Everything that can be done with a key while placing a fleet.
+const SETUP_ACTIONS: [Action; 7] = [
+    Action::Fire,
+    Action::Quit,
+    Action::MoveUp,
+    Action::MoveLeft,
+    Action::MoveDown,
+    Action::MoveRight,
+    Action::Rotate,
+];
+
+// Where a ship goes when dragged to `cell`, pulled back far enough to stay
+// on the board.
+fn fit_ship(ship: Ship, cell: Coordinates, width: u16, height: u16) -> Coordinates {
+    let (length_x, length_y) = match ship.heading {
+        Heading::East => (ship.length, 1),
+        Heading::South => (1, ship.length),
+    };
+    Coordinates {
+        x: cell.x.min(width.saturating_sub(length_x)),
+        y: cell.y.min(height.saturating_sub(length_y)),
+    }
+}
+
+// Every opponent's board, with their ships shown while they're placed.
+fn opponent_boards(
+    game: &Game,
+    layout: &Layout,
+    opponents: &[Faction],
+) -> (Vec<(LabelView, BoardView, ScoresView)>, Vec<ShipView>) {
+    let mut views = vec![];
+    let mut ship_views = vec![];
+    for (index, faction) in opponents.iter().enumerate() {
+        let board_view = BoardView::new(layout.opponent_board(index), game.board.clone());
+        for ship in game.fleet(*faction).ships.iter() {
+            ship_views.push(ShipView::new(board_view.origin, *ship));
+        }
+        views.push((
+            LabelView::new(
+                layout.opponent_title(index),
+                Label::new(faction.name().to_string()),
+            ),
+            board_view,
+            ScoresView::new(layout.opponent_scores(index), game.fleet(*faction).damage()),
+        ));
+    }
+    (views, ship_views)
+}
+
+pub fn setup_controller(game: &mut Game, events: &Events) {
     let mut stdout = stdout().into_raw_mode().unwrap();
-    let stdin = stdin();
 
     write!(
         stdout,
@@ -25,100 +75,210 @@ pub fn setup_controller(game: &mut Game) {
 
     // Models
     let title = Label::new("Rustbuckets 0.1.0".to_string());
-    let red_board_title = Label::new("Red Team".to_string());
-    let blue_board_title = Label::new("Blue Team".to_string());
-    let red_board = Board::new(game.width, game.height);
-    let blue_board = Board::new(game.width, game.height);
-    let alert = Alert::new("Blue Cmdr, place your ships!".to_string(), Level::Info);
-
-    // Setup AI ships
-    // Toggle to red player
-    game.toggle_active_player();
-    for length in vec![2, 2, 3, 4, 5] {
-        let ship = game
-            .auto_create_ship(length)
-            .expect("Should have been able to create the ship");
-        game.place_ship(ship)
-            .expect("Should have been able to place the ship!");
+    let opponents: Vec<Faction> = game
+        .fleets
+        .iter()
+        .map(|fleet| fleet.faction)
+        .filter(|faction| *faction != Faction::Blue)
+        .collect();
+    let mut layout = Layout::new(&game.board, opponents.len());
+    let alert = Alert::new(
+        format!("{}, place your ships!", game.profile.name),
+        Level::Info,
+    );
+
+    // Everything each commander places, in reverse order of placement.
+    let mut fleet = Placeable::fleet();
+    fleet.reverse();
+
+    // Setup AI ships for every other faction
+    for faction in opponents.iter() {
+        game.active_player = *faction;
+        game.place_bot_fleet()
+            .expect("Should have been able to place the fleet!");
     }
-    // Toggle to blue player
-    game.toggle_active_player();
+    // Back to the blue player
+    game.active_player = Faction::Blue;
 
     // Views
     let title_view = LabelView::new(Coordinates { x: 1, y: 1 }, title);
-    let red_board_title_view = LabelView::new(Coordinates { x: 1, y: 3 }, red_board_title);
-    let red_board_view = BoardView::new(Coordinates { x: 1, y: 4 }, red_board);
-    let mut alert_view = AlertView::new(Coordinates { x: 1, y: 23 }, alert);
-    let blue_board_title_view = LabelView::new(Coordinates { x: 1, y: 27 }, blue_board_title);
-    let blue_board_view = BoardView::new(Coordinates { x: 1, y: 28 }, blue_board);
-    let mut red_ship_views: Vec<ShipView> = vec![];
+    let mut alert_view = AlertView::new(layout.alert(), alert);
+    let (mut opponent_views, mut opponent_ship_views) = opponent_boards(game, &layout, &opponents);
+    let mut blue_board_title_view = LabelView::new(
+        layout.player_title(),
+        Label::new(game.faction_name(Faction::Blue)),
+    );
+    let mut blue_board_view = BoardView::new(layout.player_board(), game.board.clone());
     let mut blue_ship_views: Vec<ShipView> = vec![];
-    let red_team_score_view = ScoresView::new(Coordinates { x: 36, y: 0 }, game.red_score);
-    let blue_team_score_view = ScoresView::new(Coordinates { x: 36, y: 24 }, game.blue_score);
-    for ship in game.red_ships.iter() {
-        red_ship_views.push(ShipView::new(
-            Coordinates {
-                x: red_board_view.origin.x,
-                y: red_board_view.origin.y,
-            },
-            ship.clone(),
-        ))
-    }
+    let mut blue_team_score_view =
+        ScoresView::new(layout.player_scores(), game.fleet(Faction::Blue).damage());
 
     // Initial render
-    title_view.render(&mut stdout);
-    alert_view.render(&mut stdout);
-    red_board_title_view.render(&mut stdout);
-    red_board_view.render(&mut stdout);
-    blue_board_title_view.render(&mut stdout);
-    blue_board_view.render(&mut stdout);
-    red_team_score_view.render(&mut stdout);
-    blue_team_score_view.render(&mut stdout);
-    for ship_view in red_ship_views.iter() {
-        ship_view.render(&mut stdout);
+    if layout.fits() {
+        title_view.render(&mut stdout);
+        alert_view.render(&mut stdout);
+        for (title_view, board_view, score_view) in opponent_views.iter() {
+            title_view.render(&mut stdout);
+            board_view.render(&mut stdout);
+            score_view.render(&mut stdout);
+        }
+        blue_board_title_view.render(&mut stdout);
+        blue_board_view.render(&mut stdout);
+        blue_team_score_view.render(&mut stdout);
+        for ship_view in opponent_ship_views.iter() {
+            ship_view.render(&mut stdout);
+        }
+    } else {
+        TooSmallView::new(layout.required(), layout.terminal()).render(&mut stdout);
     }
 
     // Preamble for letting players place their own ships
-    let mut ship_lengths_to_place = vec![2, 2, 3, 4, 5];
-    let new_ship_length = ship_lengths_to_place.pop().unwrap();
+    let mut to_place = fleet;
+    let mut placing = to_place.pop().unwrap();
     let mut new_ship = Ship {
-        length: new_ship_length,
+        length: match placing {
+            Placeable::Ship(length) => length,
+            Placeable::Mine => 1,
+        },
         ..Ship::default()
     };
     let mut new_ship_view = ShipView::new(blue_board_view.origin, new_ship);
+    let mut new_mine = Mine::new(Coordinates::default());
+    let mut new_mine_view = MineView::new(blue_board_view.origin, new_mine);
+    let mut blue_mine_views: Vec<MineView> = vec![];
+
+    let keymap = Keymap::current(game.profile.keybindings);
+    // Whether the left button went down over the board, so letting go
+    // should place whatever is being dragged.
+    let mut dragging = false;
 
-    new_ship_view.render(&mut stdout);
+    if layout.fits() {
+        new_ship_view.render(&mut stdout);
+    }
 
     stdout.flush().unwrap();
 
-    for c in stdin.keys() {
-        match c.unwrap() {
-            Key::Char('f') => {
-                match game.place_ship(new_ship) {
-                    Ok(_) => match ship_lengths_to_place.pop() {
-                        Some(length) => {
-                            alert_view = alert_view.update(Alert::new(
-                                format!("Place a ship with length: {}!", length),
-                                Level::Info,
-                            ));
-                            alert_view.render(&mut stdout);
-                            stdout.flush().unwrap();
-                            blue_ship_views.push(ShipView::new(blue_board_view.origin, new_ship));
-
-                            new_ship = Ship {
-                                length: length,
-                                ..Ship::default()
-                            };
-                            new_ship_view = ShipView::new(blue_board_view.origin, new_ship);
+    for event in events.inputs() {
+        let action = match event {
+            Event::Key(key) => keymap.action(key, &SETUP_ACTIONS),
+            // Drag the ship or mine around the board with the left button,
+            // let go to place it, and right click to turn a ship.
+            Event::Mouse(MouseEvent::Press(MouseButton::Left, x, y))
+            | Event::Mouse(MouseEvent::Hold(x, y)) => {
+                let cell = match cell_at(blue_board_view.origin, &game.board, x, y) {
+                    Some(cell) => cell,
+                    None => continue,
+                };
+                dragging = true;
+                match placing {
+                    Placeable::Ship(_) => {
+                        new_ship.origin =
+                            fit_ship(new_ship, cell, game.board.width, game.board.height);
+                        new_ship_view = new_ship_view.update(new_ship);
+                    }
+                    Placeable::Mine => {
+                        new_mine = Mine::new(cell);
+                        new_mine_view = new_mine_view.update(new_mine);
+                    }
+                }
+                None
+            }
+            Event::Mouse(MouseEvent::Release(..)) if dragging => {
+                dragging = false;
+                Some(Action::Fire)
+            }
+            Event::Mouse(MouseEvent::Press(MouseButton::Right, ..)) => Some(Action::Rotate),
+            // Everything moves to suit the new size, so start from a blank
+            // screen.
+            Event::Resize => {
+                layout = Layout::new(&game.board, opponents.len());
+                write!(stdout, "{}", termion::clear::All).unwrap();
+                alert_view.origin = layout.alert();
+                let (views, ship_views) = opponent_boards(game, &layout, &opponents);
+                opponent_views = views;
+                opponent_ship_views = ship_views;
+                blue_board_title_view = LabelView::new(
+                    layout.player_title(),
+                    Label::new(game.faction_name(Faction::Blue)),
+                );
+                blue_board_view.origin = layout.player_board();
+                blue_team_score_view =
+                    ScoresView::new(layout.player_scores(), game.fleet(Faction::Blue).damage());
+                let blue_fleet = game.fleet(Faction::Blue);
+                blue_ship_views = blue_fleet
+                    .ships
+                    .iter()
+                    .map(|ship| ShipView::new(blue_board_view.origin, *ship))
+                    .collect();
+                blue_mine_views = blue_fleet
+                    .mines
+                    .iter()
+                    .map(|mine| MineView::new(blue_board_view.origin, *mine))
+                    .collect();
+                new_ship_view = ShipView::new(blue_board_view.origin, new_ship);
+                new_mine_view = MineView::new(blue_board_view.origin, new_mine);
+                None
+            }
+            _ => continue,
+        };
+        match action {
+            Some(Action::Fire) => {
+                let placed = match placing {
+                    Placeable::Ship(_) => game.place_ship(new_ship),
+                    Placeable::Mine => game.place_mine(new_mine),
+                };
+                match placed {
+                    Ok(_) => {
+                        match placing {
+                            Placeable::Ship(_) => {
+                                blue_ship_views
+                                    .push(ShipView::new(blue_board_view.origin, new_ship));
+                            }
+                            Placeable::Mine => {
+                                blue_mine_views
+                                    .push(MineView::new(blue_board_view.origin, new_mine));
+                            }
                         }
-                        None => {
-                            game.switch_mode(Mode::Play);
-                            break;
+                        match to_place.pop() {
+                            Some(Placeable::Ship(length)) => {
+                                alert_view = alert_view.update(Alert::new(
+                                    format!("Place a ship with length: {}!", length),
+                                    Level::Info,
+                                ));
+                                alert_view.render(&mut stdout);
+                                stdout.flush().unwrap();
+
+                                placing = Placeable::Ship(length);
+                                new_ship = Ship {
+                                    length,
+                                    ..Ship::default()
+                                };
+                                new_ship_view = ShipView::new(blue_board_view.origin, new_ship);
+                            }
+                            Some(Placeable::Mine) => {
+                                alert_view = alert_view.update(Alert::new(
+                                    "Lay a mine in your waters!".to_string(),
+                                    Level::Info,
+                                ));
+                                alert_view.render(&mut stdout);
+                                stdout.flush().unwrap();
+
+                                placing = Placeable::Mine;
+                                new_mine = Mine::new(Coordinates::default());
+                                new_mine_view = new_mine_view.update(new_mine);
+                            }
+                            None => {
+                                game.switch_mode(Mode::Play);
+                                break;
+                            }
                         }
-                    },
+                    }
                     Err(_) => {
                         alert_view = alert_view.update(Alert::new(
-                            "You can't place a ship there!".to_string(),
+                            match placing {
+                                Placeable::Ship(_) => "You can't place a ship there!".to_string(),
+                                Placeable::Mine => "You can't lay a mine there!".to_string(),
+                            },
                             Level::Warning,
                         ));
                         alert_view.render(&mut stdout);
@@ -127,66 +287,109 @@ pub fn setup_controller(game: &mut Game) {
                     }
                 }
             }
-            Key::Char('q') => {
+            Some(Action::Quit) => {
                 game.switch_mode(Mode::Title);
                 break;
             }
-            Key::Char('w') => {
-                if new_ship.origin.y > 0 {
+            Some(Action::MoveUp) => match placing {
+                Placeable::Ship(_) if new_ship.origin.y > 0 => {
                     new_ship = new_ship.move_up();
                     new_ship_view = new_ship_view.update(new_ship);
                 }
-            }
-            Key::Char('a') => {
-                if new_ship.origin.x > 0 {
+                Placeable::Mine if new_mine.coordinates.y > 0 => {
+                    new_mine = Mine::new(new_mine.coordinates.get_above());
+                    new_mine_view = new_mine_view.update(new_mine);
+                }
+                _ => {}
+            },
+            Some(Action::MoveLeft) => match placing {
+                Placeable::Ship(_) if new_ship.origin.x > 0 => {
                     new_ship = new_ship.move_left();
                     new_ship_view = new_ship_view.update(new_ship);
                 }
-            }
-            Key::Char('s') => {
-                let should_move = match new_ship.heading {
-                    Heading::South => new_ship.length + new_ship.origin.y < game.height,
-                    Heading::East => 1 + new_ship.origin.y < game.height,
-                };
+                Placeable::Mine if new_mine.coordinates.x > 0 => {
+                    new_mine = Mine::new(new_mine.coordinates.get_left());
+                    new_mine_view = new_mine_view.update(new_mine);
+                }
+                _ => {}
+            },
+            Some(Action::MoveDown) => match placing {
+                Placeable::Ship(_) => {
+                    let should_move = match new_ship.heading {
+                        Heading::South => new_ship.length + new_ship.origin.y < game.board.height,
+                        Heading::East => 1 + new_ship.origin.y < game.board.height,
+                    };
 
-                if should_move {
-                    new_ship = new_ship.move_down();
-                    new_ship_view = new_ship_view.update(new_ship);
+                    if should_move {
+                        new_ship = new_ship.move_down();
+                        new_ship_view = new_ship_view.update(new_ship);
+                    }
                 }
-            }
-            Key::Char('d') => {
-                let should_move = match new_ship.heading {
-                    Heading::South => 1 + new_ship.origin.y < game.height,
-                    Heading::East => new_ship.length + new_ship.origin.x < game.height,
-                };
+                Placeable::Mine => {
+                    if 1 + new_mine.coordinates.y < game.board.height {
+                        new_mine = Mine::new(new_mine.coordinates.get_below());
+                        new_mine_view = new_mine_view.update(new_mine);
+                    }
+                }
+            },
+            Some(Action::MoveRight) => match placing {
+                Placeable::Ship(_) => {
+                    let should_move = match new_ship.heading {
+                        Heading::South => 1 + new_ship.origin.x < game.board.width,
+                        Heading::East => new_ship.length + new_ship.origin.x < game.board.width,
+                    };
 
-                if should_move {
-                    new_ship = new_ship.move_right();
+                    if should_move {
+                        new_ship = new_ship.move_right();
+                        new_ship_view = new_ship_view.update(new_ship);
+                    }
+                }
+                Placeable::Mine => {
+                    if 1 + new_mine.coordinates.x < game.board.width {
+                        new_mine = Mine::new(new_mine.coordinates.get_right());
+                        new_mine_view = new_mine_view.update(new_mine);
+                    }
+                }
+            },
+            Some(Action::Rotate) => {
+                if let Placeable::Ship(_) = placing {
+                    new_ship = new_ship.flip();
                     new_ship_view = new_ship_view.update(new_ship);
                 }
             }
-            Key::Char('r') => {
-                new_ship = new_ship.flip();
-                new_ship_view = new_ship_view.update(new_ship);
-            }
             _ => {}
         }
 
+        if !layout.fits() {
+            TooSmallView::new(layout.required(), layout.terminal()).render(&mut stdout);
+            stdout.flush().unwrap();
+            continue;
+        }
+
         // Rerender
         title_view.render(&mut stdout);
-        red_board_title_view.render(&mut stdout);
-        red_board_view.render(&mut stdout);
+        alert_view.render(&mut stdout);
+        for (title_view, board_view, score_view) in opponent_views.iter() {
+            title_view.render(&mut stdout);
+            board_view.render(&mut stdout);
+            score_view.render(&mut stdout);
+        }
         blue_board_title_view.render(&mut stdout);
         blue_board_view.render(&mut stdout);
-        red_team_score_view.render(&mut stdout);
         blue_team_score_view.render(&mut stdout);
-        for ship_view in red_ship_views.iter() {
+        for ship_view in opponent_ship_views.iter() {
             ship_view.render(&mut stdout);
         }
         for ship_view in blue_ship_views.iter() {
             ship_view.render(&mut stdout);
         }
-        new_ship_view.render(&mut stdout);
+        for mine_view in blue_mine_views.iter() {
+            mine_view.render(&mut stdout);
+        }
+        match placing {
+            Placeable::Ship(_) => new_ship_view.render(&mut stdout),
+            Placeable::Mine => new_mine_view.render(&mut stdout),
+        }
 
         stdout.flush().unwrap();
     }
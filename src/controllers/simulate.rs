@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use crate::models::{Difficulty, Engine, Faction, Game, Ratings};
+
+// Turns after which a simulated game is called off as a draw.
+const TURN_LIMIT: usize = 2000;
+
+// Lets the bots fight it out until someone wins or it's called a draw.
+pub fn play_out(game: &mut Game) {
+    for _ in 0..TURN_LIMIT {
+        if game.auto_play_turn().is_err() || game.winner().is_some() {
+            break;
+        }
+        game.next_player();
+    }
+}
+
+// Plays `games` games between bots of the given difficulties without a
+// screen, each set up by `new_game` for that many fleets, then prints how
+// each bot did and where the ratings stand. Any fleet given an engine is
+// played by it instead, falling back to the bot when it misbehaves. Stops
+// without saving the ratings if an engine won't start.
+pub fn simulate_controller(
+    games: u32,
+    bots: &[Difficulty],
+    new_game: &dyn Fn(usize) -> Game,
+    engines: &[(Faction, String)],
+    engine_time: Duration,
+) -> Result<(), String> {
+    let mut ratings = Ratings::load();
+    let mut wins = vec![0; bots.len()];
+    let mut names: Vec<String> = bots
+        .iter()
+        .map(|difficulty| format!("Bot ({})", difficulty.name()))
+        .collect();
+    let mut draws = 0;
+
+    for _ in 0..games {
+        let mut game = new_game(bots.len());
+        for (fleet, difficulty) in game.fleets.iter_mut().zip(bots.iter()) {
+            fleet.difficulty = Some(*difficulty);
+        }
+        for (faction, command) in engines.iter() {
+            let engine = Engine::spawn(command, engine_time)
+                .map_err(|message| format!("{}: {}", command, message))?;
+            game.fleet_mut(*faction).engine = Some(engine);
+        }
+        for (seat, faction) in Faction::all().into_iter().take(bots.len()).enumerate() {
+            names[seat] = game.rating_name(faction);
+        }
+        for faction in Faction::all().into_iter().take(bots.len()) {
+            game.active_player = faction;
+            game.place_bot_fleet()
+                .expect("Should have been able to place the fleet!");
+        }
+        game.active_player = Faction::Blue;
+
+        play_out(&mut game);
+
+        match game.winner() {
+            Some(winner) => {
+                let seat = game
+                    .fleets
+                    .iter()
+                    .position(|fleet| fleet.faction == winner)
+                    .unwrap();
+                wins[seat] += 1;
+                game.rate(&mut ratings);
+            }
+            None => draws += 1,
+        }
+    }
+
+    println!("{} games, {} drawn", games, draws);
+    for (seat, name) in names.iter().enumerate() {
+        println!(
+            "{:<12}{:<20}{} wins",
+            Faction::all()[seat].name(),
+            name,
+            wins[seat]
+        );
+    }
+    println!();
+    println!("Ratings");
+    for entry in ratings.leaderboard().iter() {
+        println!(
+            "{:<20}{:>6.0}  ({} games)",
+            entry.name, entry.rating, entry.games
+        );
+    }
+    if let Err(message) = ratings.save() {
+        eprintln!("{}", message);
+    }
+    Ok(())
+}
@@ -0,0 +1,102 @@
+use std::io::{stdout, Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::{IntoRawMode, RawTerminal};
+
+use crate::{
+    controllers::{Action, Event, Events, Keymap, Mode},
+    models::{Coordinates, Difficulty, Game, Label, Records},
+    views::LabelView,
+};
+
+pub fn stats_controller(game: &mut Game, events: &Events) {
+    let mut stdout = stdout().into_raw_mode().unwrap();
+
+    // Clear the screen
+    write!(
+        stdout,
+        "{}{}{}",
+        termion::clear::All,
+        Goto(1, 1),
+        termion::cursor::Hide
+    )
+    .unwrap();
+
+    // Models
+    let keymap = Keymap::current(game.profile.keybindings);
+    let records = Records::load().for_profile(&game.profile.name);
+    let summary = records.summary();
+    let mut lines = vec![
+        "Rustbuckets 0.1.0".to_string(),
+        format!("Press {} to go back", keymap.describe(Action::Quit)),
+        "".to_string(),
+        format!(
+            "Games played: {}   Won: {} ({}%)",
+            summary.played,
+            summary.won,
+            summary.win_rate()
+        ),
+        format!("Average accuracy: {}%", summary.accuracy),
+        match records.best_game() {
+            Some(best) => format!(
+                "Best game: won on {} in {} turns ({}% accuracy, {}m {:02}s)",
+                best.difficulty.name(),
+                best.turns,
+                best.accuracy(),
+                best.duration.as_secs() / 60,
+                best.duration.as_secs() % 60
+            ),
+            None => "Best game: no wins yet".to_string(),
+        },
+        "".to_string(),
+    ];
+    for difficulty in Difficulty::all().into_iter() {
+        let summary = records.summary_for(difficulty);
+        lines.push(format!(
+            "{:<8}Played: {:<5}Won: {:<5}({}%)   Accuracy: {}%",
+            difficulty.name(),
+            summary.played,
+            summary.won,
+            summary.win_rate(),
+            summary.accuracy
+        ));
+    }
+
+    // Views
+    let views: Vec<LabelView> = lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            LabelView::new(
+                Coordinates {
+                    x: 1,
+                    y: 1 + index as u16,
+                },
+                Label::new(line),
+            )
+        })
+        .collect();
+    let render = |stdout: &mut RawTerminal<Stdout>| {
+        for view in views.iter() {
+            view.render(stdout);
+        }
+        stdout.flush().unwrap();
+    };
+    render(&mut stdout);
+
+    for event in events.inputs() {
+        let key = match event {
+            Event::Key(key) => key,
+            // Resizing can leave the old screen garbled, so draw it afresh.
+            Event::Resize => {
+                write!(stdout, "{}", termion::clear::All).unwrap();
+                render(&mut stdout);
+                continue;
+            }
+            _ => continue,
+        };
+        if let Some(Action::Quit) = keymap.action(key, &[Action::Quit]) {
+            game.switch_mode(Mode::Title);
+            break;
+        }
+    }
+}
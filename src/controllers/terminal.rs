@@ -0,0 +1,56 @@
+use std::io::{stdout, Stdout, Write};
+use std::panic;
+use termion::input::MouseTerminal;
+use termion::screen::{AlternateScreen, ToMainScreen};
+
+// What MouseTerminal writes when it's dropped, to stop reporting clicks.
+const DISABLE_MOUSE: &str = "\x1b[?1006l\x1b[?1015l\x1b[?1002l\x1b[?1000l";
+
+// Holds the terminal for as long as the game runs. Play happens on the
+// alternate screen with clicks reported, and dropping this puts the
+// player's shell back as it was, cursor and all.
+pub struct Terminal {
+    _screen: AlternateScreen<MouseTerminal<Stdout>>,
+}
+
+impl Terminal {
+    pub fn new() -> Terminal {
+        restore_on_panic();
+        Terminal {
+            _screen: AlternateScreen::from(MouseTerminal::from(stdout())),
+        }
+    }
+}
+
+impl Drop for Terminal {
+    fn drop(&mut self) {
+        let mut stdout = stdout();
+        write!(stdout, "{}", termion::cursor::Show).unwrap();
+        stdout.flush().unwrap();
+    }
+}
+
+// A panic prints its message before anything is dropped, which would be in
+// raw mode on the alternate screen, and gone as soon as the screen
+// switches back. So put the terminal back first, from the settings it had
+// before any screen went raw.
+fn restore_on_panic() {
+    let mut original: libc::termios = unsafe { std::mem::zeroed() };
+    let saved = unsafe { libc::tcgetattr(libc::STDOUT_FILENO, &mut original) } == 0;
+    let report = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        if saved {
+            unsafe { libc::tcsetattr(libc::STDOUT_FILENO, libc::TCSANOW, &original) };
+        }
+        let mut stdout = stdout();
+        let _ = write!(
+            stdout,
+            "{}{}{}",
+            DISABLE_MOUSE,
+            ToMainScreen,
+            termion::cursor::Show
+        );
+        let _ = stdout.flush();
+        report(info);
+    }));
+}
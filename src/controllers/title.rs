@@ -1,18 +1,33 @@
-use std::io::{stdin, stdout, Write};
+use std::io::{stdout, Write};
 use termion::cursor::Goto;
-use termion::event::Key;
-use termion::input::TermRead;
 use termion::raw::IntoRawMode;
 
 use crate::{
-    controllers::Mode,
-    models::{Coordinates, Game, Label},
+    controllers::{Action, Events, Keymap, Mode},
+    models::{Coordinates, Difficulty, Game, Label, Profiles, Ratings},
     views::LabelView,
 };
 
-pub fn title_controller(game: &mut Game) {
+// Everything that can be done with a key on the title screen.
+const TITLE_ACTIONS: [Action; 6] = [
+    Action::Start,
+    Action::Quit,
+    Action::Stats,
+    Action::Achievements,
+    Action::Profiles,
+    Action::Difficulty,
+];
+
+fn describe_difficulty(difficulty: Difficulty, keymap: &Keymap) -> String {
+    format!(
+        "Difficulty: {:<8}(press {} to change)",
+        difficulty.name(),
+        keymap.describe(Action::Difficulty)
+    )
+}
+
+pub fn title_controller(game: &mut Game, events: &Events) {
     let mut stdout = stdout().into_raw_mode().unwrap();
-    let stdin = stdin();
 
     // Clear the screen
     write!(
@@ -26,31 +41,108 @@ pub fn title_controller(game: &mut Game) {
 
     // Models
     let title = Label::new("Rustbuckets 0.1.0".to_string());
-    let play_instructions = Label::new("Press F to start".to_string());
-    let quit_instructions = Label::new("Press Q to quit".to_string());
+    let keymap = Keymap::current(game.profile.keybindings);
+    let play_instructions =
+        Label::new(format!("Press {} to start", keymap.describe(Action::Start)));
+    let quit_instructions = Label::new(format!("Press {} to quit", keymap.describe(Action::Quit)));
+    let stats_instructions = Label::new(format!(
+        "Press {} for your stats",
+        keymap.describe(Action::Stats)
+    ));
+    let achievements_instructions = Label::new(format!(
+        "Press {} for your achievements",
+        keymap.describe(Action::Achievements)
+    ));
+    let mut difficulty = Label::new(describe_difficulty(game.difficulty, &keymap));
+    let ratings = Ratings::load();
+    let profile = Label::new(format!(
+        "Profile: {}   Rating: {:.0}   (press {} to change)",
+        game.profile.name,
+        ratings.rating(&game.profile.name),
+        keymap.describe(Action::Profiles)
+    ));
+    let mut leaderboard = vec![Label::new("Leaderboard".to_string())];
+    for entry in ratings.leaderboard().iter().take(5) {
+        leaderboard.push(Label::new(format!(
+            "{:<20}{:>6.0}  ({} games)",
+            entry.name, entry.rating, entry.games
+        )));
+    }
 
     // Views
     let title_view = LabelView::new(Coordinates { x: 1, y: 1 }, title);
     let play_instructions_view = LabelView::new(Coordinates { x: 1, y: 2 }, play_instructions);
     let quit_instructions_view = LabelView::new(Coordinates { x: 1, y: 3 }, quit_instructions);
+    let stats_instructions_view = LabelView::new(Coordinates { x: 1, y: 4 }, stats_instructions);
+    let achievements_instructions_view =
+        LabelView::new(Coordinates { x: 1, y: 5 }, achievements_instructions);
+    let mut difficulty_view = LabelView::new(Coordinates { x: 1, y: 6 }, difficulty);
+    let profile_view = LabelView::new(Coordinates { x: 1, y: 7 }, profile);
+    let leaderboard_views: Vec<LabelView> = leaderboard
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            LabelView::new(
+                Coordinates {
+                    x: 1,
+                    y: 9 + index as u16,
+                },
+                line,
+            )
+        })
+        .collect();
 
     // Initial render
     title_view.render(&mut stdout);
     play_instructions_view.render(&mut stdout);
     quit_instructions_view.render(&mut stdout);
+    stats_instructions_view.render(&mut stdout);
+    achievements_instructions_view.render(&mut stdout);
+    difficulty_view.render(&mut stdout);
+    profile_view.render(&mut stdout);
+    // Only worth showing once somebody has a rating.
+    if leaderboard_views.len() > 1 {
+        for view in leaderboard_views.iter() {
+            view.render(&mut stdout);
+        }
+    }
 
     stdout.flush().unwrap();
 
-    for c in stdin.keys() {
-        match c.unwrap() {
-            Key::Char('f') => {
+    for key in events.keys() {
+        match keymap.action(key, &TITLE_ACTIONS) {
+            Some(Action::Start) => {
                 game.switch_mode(Mode::Setup);
                 break;
             }
-            Key::Char('q') => {
+            Some(Action::Quit) => {
                 game.switch_mode(Mode::Exit);
                 break;
             }
+            Some(Action::Stats) => {
+                game.switch_mode(Mode::Stats);
+                break;
+            }
+            Some(Action::Achievements) => {
+                game.switch_mode(Mode::Achievements);
+                break;
+            }
+            Some(Action::Profiles) => {
+                game.switch_mode(Mode::Profiles);
+                break;
+            }
+            Some(Action::Difficulty) => {
+                game.difficulty = game.difficulty.next();
+                // The pick sticks as the profile's default.
+                game.profile.difficulty = game.difficulty;
+                let mut profiles = Profiles::load();
+                profiles.update(game.profile.clone());
+                let _ = profiles.save();
+                difficulty = Label::new(describe_difficulty(game.difficulty, &keymap));
+                difficulty_view = LabelView::new(Coordinates { x: 1, y: 6 }, difficulty);
+                difficulty_view.render(&mut stdout);
+                stdout.flush().unwrap();
+            }
             _ => {}
         }
     }
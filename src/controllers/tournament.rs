@@ -0,0 +1,145 @@
+use std::{fs, time::Duration};
+
+use super::simulate::play_out;
+use crate::models::{seed, Engine, Entrant, Faction, Game, Stats, Tournament};
+
+// Plays one seeded game with `blue` against `red`, returning the winner's
+// seat and the shots it took, or None for a draw. Fails if an engine won't
+// start, rather than let a built-in bot play under its name.
+fn play_game(
+    entrants: &[Entrant],
+    (blue, red): (usize, usize),
+    game_seed: u64,
+    new_game: &dyn Fn(usize) -> Game,
+    engine_time: Duration,
+    names: &mut [String],
+) -> Result<Option<(usize, u16)>, String> {
+    let mut engines = vec![];
+    for entrant in [blue, red].iter() {
+        engines.push(match &entrants[*entrant] {
+            Entrant::Engine(command) => Some(
+                Engine::spawn(command, engine_time)
+                    .map_err(|message| format!("{}: {}", command, message))?,
+            ),
+            Entrant::Bot(_) => None,
+        });
+    }
+
+    seed(Some(game_seed));
+    let mut game = new_game(2);
+    let seats = [(Faction::Blue, blue), (Faction::Red, red)];
+    for ((faction, entrant), engine) in seats.iter().zip(engines) {
+        let fleet = game.fleet_mut(*faction);
+        fleet.engine = engine;
+        if let Entrant::Bot(difficulty) = &entrants[*entrant] {
+            fleet.difficulty = Some(*difficulty);
+        }
+    }
+    for (faction, entrant) in [(Faction::Blue, blue), (Faction::Red, red)].iter() {
+        // Engines only know what they're called once they've started.
+        names[*entrant] = game.rating_name(*faction);
+        game.active_player = *faction;
+        game.place_bot_fleet()
+            .expect("Should have been able to place the fleet!");
+    }
+    game.active_player = Faction::Blue;
+
+    play_out(&mut game);
+    seed(None);
+
+    Ok(game.winner().map(|winner| {
+        let shots = Stats::new(&game.history, winner).shots;
+        (if winner == Faction::Blue { blue } else { red }, shots)
+    }))
+}
+
+fn print_table(tournament: &Tournament) {
+    println!(
+        "{} entrants, {} seeds per pairing played from both sides, seed {}",
+        tournament.names.len(),
+        tournament.games,
+        tournament.seed
+    );
+    println!();
+    print!("{:<24}", "");
+    for column in 1..=tournament.names.len() {
+        print!("{:>9}", format!("#{}", column));
+    }
+    println!("{:>8}{:>12}", "Win %", "Avg shots");
+    for (entrant, name) in tournament.names.iter().enumerate() {
+        let label: String = format!("#{} {}", entrant + 1, name)
+            .chars()
+            .take(23)
+            .collect();
+        print!("{:<24}", label);
+        for opponent in 0..tournament.names.len() {
+            let cell = match tournament.head_to_head(entrant, opponent) {
+                _ if opponent == entrant => "-".to_string(),
+                (wins, losses, 0) => format!("{}-{}", wins, losses),
+                (wins, losses, draws) => format!("{}-{}-{}", wins, losses, draws),
+            };
+            print!("{:>9}", cell);
+        }
+        println!(
+            "{:>8.1}{:>12}",
+            tournament.win_percentage(entrant),
+            tournament
+                .average_shots_to_win(entrant)
+                .map(|shots| format!("{:.1}", shots))
+                .unwrap_or_else(|| "-".to_string())
+        );
+    }
+}
+
+// Plays every entrant against every other, `games` seeds a pairing with
+// each seed played from both sides, then prints a crosstable and writes it
+// to `report` as CSV or JSON, going by the file's extension. Each game is
+// set up by `new_game`. Stops without a table if an engine won't start.
+pub fn tournament_controller(
+    games: u32,
+    entrants: &[Entrant],
+    first_seed: u64,
+    new_game: &dyn Fn(usize) -> Game,
+    engine_time: Duration,
+    report: Option<&str>,
+) -> Result<(), String> {
+    let mut names: Vec<String> = entrants.iter().map(|entrant| entrant.name()).collect();
+    let mut tournament = Tournament::new(names.clone(), first_seed, games);
+
+    for (a, b) in tournament.pairings() {
+        for game in 0..games {
+            // Every pairing sees the same oceans.
+            let game_seed = first_seed.wrapping_add(u64::from(game));
+            for sides in [(a, b), (b, a)].iter() {
+                match play_game(
+                    entrants,
+                    *sides,
+                    game_seed,
+                    new_game,
+                    engine_time,
+                    &mut names,
+                )? {
+                    Some((winner, shots)) => {
+                        let loser = if winner == sides.0 { sides.1 } else { sides.0 };
+                        tournament.record_win(winner, loser, shots);
+                    }
+                    None => tournament.record_draw(sides.0, sides.1),
+                }
+            }
+        }
+    }
+    tournament.names = names;
+
+    print_table(&tournament);
+    if let Some(path) = report {
+        let contents = if path.ends_with(".json") {
+            tournament.to_json()
+        } else {
+            tournament.to_csv()
+        };
+        if fs::write(path, contents).is_err() {
+            eprintln!("Couldn't write the report to {}", path);
+        }
+    }
+    Ok(())
+}
@@ -2,27 +2,407 @@ mod controllers;
 mod models;
 mod views;
 
+use std::{env, process, time::Duration};
+
 use controllers::{
-    endscreen_controller, game_controller, setup_controller, title_controller, Mode,
+    accessible_controller, achievements_controller, endscreen_controller, game_controller,
+    profiles_controller, protocol_controller, setup_controller, simulate_controller,
+    stats_controller, title_controller, tournament_controller, Events, Keymap, Mode, Terminal,
+};
+use models::{
+    rng, Board, Difficulty, Engine, Entrant, Faction, Game, Placement, Profile, Profiles, ShotChart,
 };
-use models::Game;
+use rand::Rng;
+use views::set_theme;
 
 fn main() {
+    // A map file fixes the terrain for every game; otherwise each game
+    // gets a freshly generated ocean.
+    let args: Vec<String> = env::args().collect();
+    let map = match args.iter().position(|arg| arg == "--map") {
+        Some(index) => match args.get(index + 1).map(|path| Board::load(path)) {
+            Some(Ok(board)) => Some(board),
+            Some(Err(message)) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+            None => {
+                eprintln!("--map expects a path to a map file");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+    let moving_fleet = args.iter().any(|arg| arg == "--moving-fleet");
+    let players = match args.iter().position(|arg| arg == "--players") {
+        Some(index) => match args.get(index + 1).map(|count| count.parse::<usize>()) {
+            Some(Ok(count)) if (2..=4).contains(&count) => count,
+            _ => {
+                eprintln!("--players expects a number from 2 to 4");
+                process::exit(1);
+            }
+        },
+        None => 2,
+    };
+
+    let turn_timer = match args.iter().position(|arg| arg == "--turn-timer") {
+        Some(index) => match args.get(index + 1).map(|seconds| seconds.parse::<u64>()) {
+            Some(Ok(seconds)) if seconds > 0 => Some(Duration::from_secs(seconds)),
+            _ => {
+                eprintln!("--turn-timer expects a number of seconds");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let ai_delay = match args.iter().position(|arg| arg == "--ai-delay") {
+        Some(index) => match args.get(index + 1).map(|millis| millis.parse::<u64>()) {
+            Some(Ok(millis)) => Some(Duration::from_millis(millis)),
+            _ => {
+                eprintln!("--ai-delay expects a number of milliseconds");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    let difficulty = match args.iter().position(|arg| arg == "--difficulty") {
+        Some(index) => match args.get(index + 1).and_then(|name| Difficulty::parse(name)) {
+            Some(difficulty) => Some(difficulty),
+            None => {
+                eprintln!("--difficulty expects one of easy, normal, hard or expert");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // The expert's thinking time grows with how many fleets it imagines.
+    let samples = match args.iter().position(|arg| arg == "--samples") {
+        Some(index) => match args.get(index + 1).map(|samples| samples.parse::<usize>()) {
+            Some(Ok(samples)) if samples > 0 => samples,
+            _ => {
+                eprintln!("--samples expects a number of fleets");
+                process::exit(1);
+            }
+        },
+        None => Game::default().samples,
+    };
+
+    // Otherwise each bot lays out its fleet the way its difficulty does.
+    let placement = match args.iter().position(|arg| arg == "--placement") {
+        Some(index) => match args.get(index + 1).and_then(|name| Placement::parse(name)) {
+            Some(placement) => Some(placement),
+            None => {
+                eprintln!("--placement expects one of random, spread, edges or evasive");
+                process::exit(1);
+            }
+        },
+        None => None,
+    };
+
+    // External engines can drive any of the computer's fleets.
+    let engine_time = match args.iter().position(|arg| arg == "--engine-time") {
+        Some(index) => match args.get(index + 1).map(|millis| millis.parse::<u64>()) {
+            Some(Ok(millis)) if millis > 0 => Duration::from_millis(millis),
+            _ => {
+                eprintln!("--engine-time expects a number of milliseconds");
+                process::exit(1);
+            }
+        },
+        None => Duration::from_secs(1),
+    };
+    let mut engines: Vec<(Faction, String)> = vec![];
+    for (index, arg) in args.iter().enumerate() {
+        if arg != "--engine" {
+            continue;
+        }
+        let engine = args
+            .get(index + 1)
+            .and_then(|spec| spec.split_once('='))
+            .and_then(|(name, command)| Faction::parse(name).map(|faction| (faction, command)));
+        match engine {
+            Some((faction, command)) => engines.push((faction, command.to_string())),
+            None => {
+                eprintln!("--engine expects a faction and a command, like red=./bot.py");
+                process::exit(1);
+            }
+        }
+    }
+    let seats = |count: usize| -> Result<(), String> {
+        for (faction, _) in engines.iter() {
+            if !Faction::all()[..count].contains(faction) {
+                return Err(format!(
+                    "There's no {} fleet in this game",
+                    faction.short_name()
+                ));
+            }
+        }
+        Ok(())
+    };
+
+    // Play as the named profile, or whoever played last.
+    let profiles = Profiles::load();
+    let profile = match args.iter().position(|arg| arg == "--name") {
+        Some(index) => match args.get(index + 1) {
+            Some(name) if Profile::is_valid_name(name) => profiles
+                .find(name.trim())
+                .cloned()
+                .unwrap_or_else(|| Profile::new(name.trim())),
+            _ => {
+                eprintln!("--name expects the name of the commander");
+                process::exit(1);
+            }
+        },
+        None => match profiles.entries.first() {
+            Some(profile) => profile.clone(),
+            None => Profile::new(&env::var("USER").unwrap_or_else(|_| "Commander".to_string())),
+        },
+    };
+
+    // A game between bots, on this run's settings.
+    let bot_game = |players: usize| -> Game {
+        Game {
+            board: match &map {
+                Some(board) => board.clone(),
+                None => Board::generate(8, 8, 3, 3),
+            },
+            samples,
+            placement,
+            ..Game::new(players)
+        }
+    };
+
+    // Bots can play each other without a screen, to see how they rank.
+    if let Some(index) = args.iter().position(|arg| arg == "--simulate") {
+        let games = match args.get(index + 1).map(|games| games.parse::<u32>()) {
+            Some(Ok(games)) => games,
+            _ => {
+                eprintln!("--simulate expects a number of games");
+                process::exit(1);
+            }
+        };
+        let bots: Vec<Option<Difficulty>> = match args.iter().position(|arg| arg == "--bots") {
+            Some(index) => match args.get(index + 1) {
+                Some(names) => names.split(',').map(Difficulty::parse).collect(),
+                None => vec![None],
+            },
+            None => Difficulty::all().into_iter().map(Some).collect(),
+        };
+        let bots: Vec<Difficulty> = match bots.into_iter().collect::<Option<Vec<_>>>() {
+            Some(bots) if (2..=4).contains(&bots.len()) => bots,
+            _ => {
+                eprintln!(
+                    "--bots expects two to four of easy, normal, hard or expert, comma separated"
+                );
+                process::exit(1);
+            }
+        };
+        if let Err(message) = seats(bots.len()) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        if let Err(message) = simulate_controller(games, &bots, &bot_game, &engines, engine_time) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Or everyone can play everyone, on repeatable oceans.
+    if let Some(index) = args.iter().position(|arg| arg == "--tournament") {
+        let games = match args.get(index + 1).map(|games| games.parse::<u32>()) {
+            Some(Ok(games)) if games > 0 => games,
+            _ => {
+                eprintln!("--tournament expects a number of games per pairing");
+                process::exit(1);
+            }
+        };
+        let mut entrants: Vec<Entrant> = vec![];
+        for (index, arg) in args.iter().enumerate() {
+            if arg != "--entrant" {
+                continue;
+            }
+            match args.get(index + 1) {
+                Some(spec) => entrants.push(Entrant::parse(spec)),
+                None => {
+                    eprintln!("--entrant expects a difficulty or an engine's command");
+                    process::exit(1);
+                }
+            }
+        }
+        if entrants.is_empty() {
+            entrants = Difficulty::all().into_iter().map(Entrant::Bot).collect();
+        }
+        if entrants.len() < 2 {
+            eprintln!("A tournament needs at least two entrants");
+            process::exit(1);
+        }
+        let seed = match args.iter().position(|arg| arg == "--seed") {
+            Some(index) => match args.get(index + 1).map(|seed| seed.parse::<u64>()) {
+                Some(Ok(seed)) => seed,
+                _ => {
+                    eprintln!("--seed expects a number");
+                    process::exit(1);
+                }
+            },
+            None => rng().gen(),
+        };
+        let report = match args.iter().position(|arg| arg == "--report") {
+            Some(index) => match args.get(index + 1) {
+                Some(path) if path.ends_with(".csv") || path.ends_with(".json") => Some(path),
+                _ => {
+                    eprintln!("--report expects a path ending in .csv or .json");
+                    process::exit(1);
+                }
+            },
+            None => None,
+        };
+        if let Err(message) = tournament_controller(
+            games,
+            &entrants,
+            seed,
+            &bot_game,
+            engine_time,
+            report.map(|path| path.as_str()),
+        ) {
+            eprintln!("{}", message);
+            process::exit(1);
+        }
+        return;
+    }
+
+    // Blue is the player's own fleet everywhere else.
+    if let Err(message) = seats(players) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+    if engines.iter().any(|(faction, _)| *faction == Faction::Blue) {
+        eprintln!("Blue is played by you; engines can only drive blue in --simulate");
+        process::exit(1);
+    }
+
+    // Starting a game makes its profile the one to pick next time.
+    let remember = |profile: &Profile| {
+        let mut profiles = Profiles::load();
+        profiles.select(profile.clone());
+        let _ = profiles.save();
+    };
+
+    // A fresh game for `previous`'s commander, on this run's settings.
+    // Fails if an engine won't start, rather than let the built-in AI play
+    // under its name.
+    let new_game = |previous: &Game| -> Result<Game, String> {
+        let mut defaults = Game::new(players);
+        for (faction, command) in engines.iter() {
+            let engine = Engine::spawn(command, engine_time)
+                .map_err(|message| format!("{}: {}", command, message))?;
+            defaults.fleet_mut(*faction).engine = Some(engine);
+        }
+        let (width, height) = (previous.profile.board_width, previous.profile.board_height);
+        let board = match &map {
+            Some(board) => board.clone(),
+            // Bigger oceans get more islands and reefs.
+            None => Board::generate(width, height, width * height / 20, width * height / 20),
+        };
+        let player_shots = ShotChart::load()
+            .counts(&previous.profile.name, board.width, board.height)
+            .map(|counts| counts.to_vec())
+            .unwrap_or_default();
+        Ok(Game {
+            board,
+            moving_fleet,
+            turn_timer,
+            ai_delay: ai_delay.unwrap_or(defaults.ai_delay),
+            // Whatever was last picked on the title screen.
+            difficulty: previous.difficulty,
+            samples,
+            placement,
+            player_shots,
+            profile: previous.profile.clone(),
+            ..defaults
+        })
+    };
+
+    // Screen readers get the game as lines of text instead.
+    if args.iter().any(|arg| arg == "--accessible") {
+        let mut game = match new_game(&Game {
+            difficulty: difficulty.unwrap_or(profile.difficulty),
+            profile,
+            ..Default::default()
+        }) {
+            Ok(game) => game,
+            Err(message) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+        };
+        remember(&game.profile);
+        accessible_controller(&mut game);
+        return;
+    }
+
+    // Scripts drive the game with plain commands and read back plain
+    // results, leaving the player's profiles alone.
+    if args.iter().any(|arg| arg == "--protocol") {
+        let mut game = match new_game(&Game {
+            difficulty: difficulty.unwrap_or(profile.difficulty),
+            profile,
+            ..Default::default()
+        }) {
+            Ok(game) => game,
+            Err(message) => {
+                eprintln!("{}", message);
+                process::exit(1);
+            }
+        };
+        protocol_controller(&mut game);
+        return;
+    }
+
+    // A broken keys file is worth hearing about before the screen clears.
+    if let Err(message) = Keymap::load(profile.keybindings) {
+        eprintln!("{}", message);
+        process::exit(1);
+    }
+
+    // Every screen shares one reader of keypresses, which also ticks over
+    // while waiting so the game can keep time.
+    let events = Events::new(Duration::from_millis(100));
+    // Take over the terminal until the game ends, reporting clicks so the
+    // player can aim and place ships with the mouse.
+    let terminal = Terminal::new();
+
     let mut game = Game {
+        difficulty: difficulty.unwrap_or(profile.difficulty),
+        profile,
         ..Default::default()
     };
 
     loop {
+        // The profile may have changed on the last screen.
+        set_theme(game.profile.theme);
         match game.mode {
-            Mode::Title => title_controller(&mut game),
+            Mode::Title => title_controller(&mut game, &events),
             Mode::Setup => {
-                game = Game {
-                    ..Default::default()
+                game = match new_game(&game) {
+                    Ok(game) => game,
+                    Err(message) => {
+                        // Put the shell back first, so the message stays.
+                        drop(terminal);
+                        eprintln!("{}", message);
+                        process::exit(1);
+                    }
                 };
-                setup_controller(&mut game)
+                remember(&game.profile);
+                setup_controller(&mut game, &events)
             }
-            Mode::Play => game_controller(&mut game),
-            Mode::Endscreen => endscreen_controller(&mut game),
+            Mode::Play => game_controller(&mut game, &events),
+            Mode::Endscreen => endscreen_controller(&mut game, &events),
+            Mode::Stats => stats_controller(&mut game, &events),
+            Mode::Profiles => profiles_controller(&mut game, &events),
+            Mode::Achievements => achievements_controller(&mut game, &events),
             Mode::Exit => {
                 break;
             }
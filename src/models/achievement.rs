@@ -0,0 +1,200 @@
+use crate::models::{AttackResult, Difficulty, Faction, Game, Play, Strike};
+
+// How many games it takes to become a veteran.
+const VETERAN_GAMES: u32 = 100;
+// The most shots allowed from finding the carrier to sinking it.
+const SHARPSHOOTER_SHOTS: usize = 5;
+
+// Milestones a player unlocks once and keeps with their profile.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Achievement {
+    // Won without losing a ship.
+    Flawless,
+    // Sank a carrier within five shots of finding it.
+    Sharpshooter,
+    // Won on Expert, the hardest difficulty.
+    Admiral,
+    // Played a hundred games.
+    Veteran,
+}
+
+impl Achievement {
+    pub fn all() -> Vec<Achievement> {
+        vec![
+            Achievement::Flawless,
+            Achievement::Sharpshooter,
+            Achievement::Admiral,
+            Achievement::Veteran,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "Flawless",
+            Achievement::Sharpshooter => "Sharpshooter",
+            Achievement::Admiral => "Admiral",
+            Achievement::Veteran => "Veteran",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Achievement::Flawless => "Win without losing a ship",
+            Achievement::Sharpshooter => "Sink the carrier in five shots",
+            Achievement::Admiral => "Win on Expert",
+            Achievement::Veteran => "Play 100 games",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Achievement> {
+        Achievement::all()
+            .into_iter()
+            .find(|achievement| achievement.name().eq_ignore_ascii_case(name))
+    }
+
+    // Everything the player earned in a finished game, given how many
+    // games they've now played in all.
+    pub fn evaluate(game: &Game, games_played: u32) -> Vec<Achievement> {
+        let won = game.winner() == Some(Faction::Blue);
+        let player = game.fleet(Faction::Blue);
+        Achievement::all()
+            .into_iter()
+            .filter(|achievement| match achievement {
+                Achievement::Flawless => {
+                    won && !player.ships.iter().any(|ship| player.is_ship_sunk(ship))
+                }
+                Achievement::Sharpshooter => game
+                    .fleets
+                    .iter()
+                    .filter(|fleet| fleet.faction != Faction::Blue)
+                    .any(|fleet| Achievement::sank_carrier_quickly(game, fleet.faction)),
+                Achievement::Admiral => won && game.difficulty == Difficulty::Expert,
+                Achievement::Veteran => games_played >= VETERAN_GAMES,
+            })
+            .collect()
+    }
+
+    // Whether the player sank `faction`'s carrier by themselves, firing no
+    // more than a handful of shots at that fleet between the first hit on
+    // it and the last.
+    fn sank_carrier_quickly(game: &Game, faction: Faction) -> bool {
+        let fleet = game.fleet(faction);
+        let carrier = match fleet.ships.iter().max_by_key(|ship| ship.length) {
+            Some(ship) if fleet.is_ship_sunk(ship) => ship,
+            _ => return false,
+        };
+        let segments = carrier.get_segment_coordinates();
+
+        // Every shot the player fired at the fleet, in order, marking the
+        // ones that landed on the carrier.
+        let on_carrier: Vec<bool> = game
+            .history
+            .iter()
+            .filter(|turn| turn.faction == Faction::Blue)
+            .filter_map(|turn| match &turn.play {
+                Play::Fire {
+                    target,
+                    strike: Strike::Attacks(attacks),
+                    ..
+                } if *target == faction => Some(attacks),
+                _ => None,
+            })
+            .flatten()
+            .map(|attack| {
+                attack.result == AttackResult::Hit && segments.contains(&attack.coordinates)
+            })
+            .collect();
+
+        let first = on_carrier.iter().position(|hit| *hit);
+        let last = on_carrier.iter().rposition(|hit| *hit);
+        let hits = on_carrier.iter().filter(|hit| **hit).count();
+        match (first, last) {
+            (Some(first), Some(last)) => {
+                hits == segments.len() && last - first < SHARPSHOOTER_SHOTS
+            }
+            _ => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Attack, Coordinates, Heading, Ship, Turn, Weapon};
+
+    // A two fleet game where Red has just a carrier along the top row.
+    fn carrier_game() -> Game {
+        let mut game = Game {
+            active_player: Faction::Red,
+            ..Default::default()
+        };
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 5))
+            .unwrap();
+        game.active_player = Faction::Blue;
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 7 }, Heading::East, 2))
+            .unwrap();
+        game
+    }
+
+    // Has the player fire a shot at Red for each of `xs` along row `y`.
+    fn fire(game: &mut Game, xs: &[u16], y: u16) {
+        for x in xs.iter() {
+            let coordinates = Coordinates { x: *x, y };
+            let attack = Attack::new(&game.fleet(Faction::Red).ships, coordinates);
+            game.fleet_mut(Faction::Red).attacks.push(attack);
+            game.history.push(Turn::new(
+                Faction::Blue,
+                Play::Fire {
+                    weapon: Weapon::Shot,
+                    target: Faction::Red,
+                    strike: Strike::Attacks(vec![attack]),
+                },
+            ));
+        }
+    }
+
+    #[test]
+    fn test_a_clean_sweep_earns_flawless_and_sharpshooter() {
+        let mut game = carrier_game();
+        fire(&mut game, &[3, 4], 4);
+        fire(&mut game, &[0, 1, 2, 3, 4], 0);
+        assert_eq!(
+            Achievement::evaluate(&game, 1),
+            vec![Achievement::Flawless, Achievement::Sharpshooter]
+        );
+    }
+
+    #[test]
+    fn test_misses_after_finding_the_carrier_cost_sharpshooter() {
+        let mut game = carrier_game();
+        fire(&mut game, &[0, 1], 0);
+        fire(&mut game, &[5], 3);
+        fire(&mut game, &[2, 3, 4], 0);
+        assert!(!Achievement::evaluate(&game, 1).contains(&Achievement::Sharpshooter));
+    }
+
+    #[test]
+    fn test_losing_a_ship_costs_flawless() {
+        let mut game = carrier_game();
+        game.fleet_mut(Faction::Blue).attacks.extend(vec![
+            Attack::with_result(Coordinates { x: 0, y: 7 }, AttackResult::Hit),
+            Attack::with_result(Coordinates { x: 1, y: 7 }, AttackResult::Hit),
+        ]);
+        fire(&mut game, &[0, 1, 2, 3, 4], 0);
+        assert!(!Achievement::evaluate(&game, 1).contains(&Achievement::Flawless));
+    }
+
+    #[test]
+    fn test_admiral_and_veteran() {
+        let mut game = carrier_game();
+        game.difficulty = Difficulty::Expert;
+        assert!(!Achievement::evaluate(&game, 100).contains(&Achievement::Admiral));
+        fire(&mut game, &[0, 1, 2, 3, 4], 0);
+        assert!(Achievement::evaluate(&game, 100).contains(&Achievement::Admiral));
+        // Winning on Hard isn't enough.
+        game.difficulty = Difficulty::Hard;
+        assert!(!Achievement::evaluate(&game, 100).contains(&Achievement::Admiral));
+        assert!(Achievement::evaluate(&game, 100).contains(&Achievement::Veteran));
+        assert!(!Achievement::evaluate(&game, 99).contains(&Achievement::Veteran));
+    }
+}
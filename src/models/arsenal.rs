@@ -0,0 +1,43 @@
+use crate::models::Weapon;
+
+// Limited-use weapons a commander has left. Plain shots are unlimited.
+#[derive(Debug, Copy, Clone)]
+pub struct Arsenal {
+    pub radar: u16,
+    pub airstrike: u16,
+    pub torpedo: u16,
+}
+
+impl Default for Arsenal {
+    fn default() -> Self {
+        Arsenal {
+            radar: 2,
+            airstrike: 1,
+            torpedo: 1,
+        }
+    }
+}
+
+impl Arsenal {
+    pub fn remaining(&self, weapon: Weapon) -> Option<u16> {
+        match weapon {
+            Weapon::Shot => None,
+            Weapon::Radar => Some(self.radar),
+            Weapon::Airstrike => Some(self.airstrike),
+            Weapon::Torpedo => Some(self.torpedo),
+        }
+    }
+
+    pub fn has(&self, weapon: Weapon) -> bool {
+        self.remaining(weapon) != Some(0)
+    }
+
+    pub fn spend(&mut self, weapon: Weapon) {
+        match weapon {
+            Weapon::Shot => {}
+            Weapon::Radar => self.radar -= 1,
+            Weapon::Airstrike => self.airstrike -= 1,
+            Weapon::Torpedo => self.torpedo -= 1,
+        }
+    }
+}
@@ -4,23 +4,28 @@ use crate::models::{AttackResult, Coordinates, Ship};
 pub struct Attack {
     pub coordinates: Coordinates,
     pub result: AttackResult,
+    // Set once the defender has moved a ship, so a recorded miss may no
+    // longer mean the cell is empty.
+    pub stale: bool,
 }
 
 impl Attack {
-    pub fn new(ships: &Vec<Ship>, coordinates: Coordinates) -> Attack {
+    pub fn new(ships: &[Ship], coordinates: Coordinates) -> Attack {
         for ship in ships.iter() {
             for coords in ship.get_segment_coordinates().into_iter() {
                 if coords.x == coordinates.x && coords.y == coordinates.y {
-                    return Attack {
-                        coordinates,
-                        result: AttackResult::Hit,
-                    };
+                    return Attack::with_result(coordinates, AttackResult::Hit);
                 }
             }
         }
+        Attack::with_result(coordinates, AttackResult::Miss)
+    }
+
+    pub fn with_result(coordinates: Coordinates, result: AttackResult) -> Attack {
         Attack {
             coordinates,
-            result: AttackResult::Miss,
+            result,
+            stale: false,
         }
     }
 }
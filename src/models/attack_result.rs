@@ -1,5 +1,8 @@
+use crate::models::MineEffect;
+
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub enum AttackResult {
     Hit,
     Miss,
+    Mine(MineEffect),
 }
@@ -1,11 +1,257 @@
-#[derive(Debug, Default)]
+use crate::models::{rng, Coordinates, Heading, Placeable, Ship, Terrain};
+use rand::Rng;
+use std::fs;
+
+// Rows are named A to Z, so a board can't be any taller.
+const MAX_HEIGHT: u16 = 26;
+
+#[derive(Debug, Default, Clone)]
 pub struct Board {
     pub width: u16,
     pub height: u16,
+    pub terrain: Vec<Terrain>,
 }
 
 impl Board {
     pub fn new(width: u16, height: u16) -> Board {
-        Board { width, height }
+        Board {
+            width,
+            height,
+            terrain: vec![Terrain::Water; width as usize * height as usize],
+        }
+    }
+
+    pub fn generate(width: u16, height: u16, islands: u16, reefs: u16) -> Board {
+        let mut board = Board::new(width, height);
+        let mut rng = rng();
+        for (count, terrain) in [(islands, Terrain::Island), (reefs, Terrain::Reef)].iter() {
+            let mut placed = 0;
+            // Give up rather than spin forever on a crowded board.
+            for _ in 0..width * height {
+                if placed == *count {
+                    break;
+                }
+                let coordinates = Coordinates {
+                    x: rng.gen_range(0, width),
+                    y: rng.gen_range(0, height),
+                };
+                if board.terrain_at(&coordinates) == Terrain::Water {
+                    board.set_terrain(&coordinates, *terrain);
+                    placed += 1;
+                }
+            }
+        }
+        board
+    }
+
+    pub fn from_map(map: &str) -> Result<Board, &'static str> {
+        let rows: Vec<&str> = map
+            .lines()
+            .map(|line| line.trim())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if rows.len() > usize::from(MAX_HEIGHT) {
+            return Err("The map can't be taller than 26 rows");
+        }
+        let height = rows.len() as u16;
+        let width = match rows.first() {
+            Some(row) => row.chars().count() as u16,
+            None => return Err("The map is empty"),
+        };
+
+        let mut terrain = vec![];
+        for row in rows.iter() {
+            if row.chars().count() as u16 != width {
+                return Err("Every row of the map must be the same width");
+            }
+            for c in row.chars() {
+                match Terrain::from_char(c) {
+                    Some(t) => terrain.push(t),
+                    None => return Err("The map contains an unknown terrain symbol"),
+                }
+            }
+        }
+
+        Ok(Board {
+            width,
+            height,
+            terrain,
+        })
+    }
+
+    // Loads a map file, which must leave room for a whole fleet.
+    pub fn load(path: &str) -> Result<Board, &'static str> {
+        let board = match fs::read_to_string(path) {
+            Ok(map) => Board::from_map(&map)?,
+            Err(_) => return Err("Couldn't read the map file"),
+        };
+        if !board.has_room_for(&Placeable::fleet()) {
+            return Err("The map doesn't have room for a whole fleet");
+        }
+        Ok(board)
+    }
+
+    // Whether everything in `fleet` can go on the board at once.
+    pub fn has_room_for(&self, fleet: &[Placeable]) -> bool {
+        self.lay_out(fleet, &[]).is_some()
+    }
+
+    // Somewhere for every ship in `fleet` that keeps clear of the `taken`
+    // cells and leaves room for its mines, if there's any way to do it.
+    pub fn lay_out(&self, fleet: &[Placeable], taken: &[Coordinates]) -> Option<Vec<Ship>> {
+        let mut lengths: Vec<u16> = fleet
+            .iter()
+            .filter_map(|placeable| match placeable {
+                Placeable::Ship(length) => Some(*length),
+                Placeable::Mine => None,
+            })
+            .collect();
+        lengths.sort_unstable_by(|a, b| b.cmp(a));
+        let mines = fleet
+            .iter()
+            .filter(|placeable| **placeable == Placeable::Mine)
+            .count();
+        let mut occupied: Vec<bool> = self
+            .terrain
+            .iter()
+            .map(|terrain| !terrain.is_navigable())
+            .collect();
+        for cell in taken.iter().filter(|cell| self.contains(cell)) {
+            let index = self.index(cell);
+            occupied[index] = true;
+        }
+        let mut ships = vec![];
+        if self.fit(&lengths, mines, &mut occupied, &mut ships) {
+            Some(ships)
+        } else {
+            None
+        }
+    }
+
+    // Lays out the longest ship left every way it fits, backing up when
+    // the rest won't go.
+    fn fit(
+        &self,
+        lengths: &[u16],
+        mines: usize,
+        occupied: &mut Vec<bool>,
+        ships: &mut Vec<Ship>,
+    ) -> bool {
+        let (length, rest) = match lengths.split_first() {
+            Some((length, rest)) => (*length, rest),
+            None => return occupied.iter().filter(|cell| !**cell).count() >= mines,
+        };
+        for y in 0..self.height {
+            for x in 0..self.width {
+                for heading in [Heading::East, Heading::South].iter() {
+                    let ship = Ship::new(Coordinates { x, y }, *heading, length);
+                    let segments = ship.get_segment_coordinates();
+                    if segments
+                        .iter()
+                        .any(|cell| !self.contains(cell) || occupied[self.index(cell)])
+                    {
+                        continue;
+                    }
+                    let cells: Vec<usize> = segments.iter().map(|cell| self.index(cell)).collect();
+                    cells.iter().for_each(|cell| occupied[*cell] = true);
+                    ships.push(ship);
+                    if self.fit(rest, mines, occupied, ships) {
+                        return true;
+                    }
+                    ships.pop();
+                    cells.iter().for_each(|cell| occupied[*cell] = false);
+                }
+            }
+        }
+        false
+    }
+
+    pub fn contains(&self, coordinates: &Coordinates) -> bool {
+        coordinates.x < self.width && coordinates.y < self.height
+    }
+
+    pub fn terrain_at(&self, coordinates: &Coordinates) -> Terrain {
+        if self.contains(coordinates) {
+            self.terrain[self.index(coordinates)]
+        } else {
+            Terrain::Water
+        }
+    }
+
+    fn set_terrain(&mut self, coordinates: &Coordinates, terrain: Terrain) {
+        let index = self.index(coordinates);
+        self.terrain[index] = terrain;
+    }
+
+    fn index(&self, coordinates: &Coordinates) -> usize {
+        coordinates.y as usize * self.width as usize + coordinates.x as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_new_board_is_all_water() {
+        let board = Board::new(8, 8);
+        assert_eq!(board.terrain.len(), 64);
+        assert!(board.terrain.iter().all(|t| *t == Terrain::Water));
+    }
+
+    #[test]
+    fn test_generate_places_terrain() {
+        let board = Board::generate(8, 8, 3, 2);
+        let islands = board.terrain.iter().filter(|t| **t == Terrain::Island);
+        let reefs = board.terrain.iter().filter(|t| **t == Terrain::Reef);
+        assert_eq!(islands.count(), 3);
+        assert_eq!(reefs.count(), 2);
+    }
+
+    #[test]
+    fn test_from_map() {
+        let board = Board::from_map("..#\n~..\n").unwrap();
+        assert_eq!(board.width, 3);
+        assert_eq!(board.height, 2);
+        assert_eq!(
+            board.terrain_at(&Coordinates { x: 2, y: 0 }),
+            Terrain::Island
+        );
+        assert_eq!(board.terrain_at(&Coordinates { x: 0, y: 1 }), Terrain::Reef);
+        assert_eq!(
+            board.terrain_at(&Coordinates { x: 1, y: 1 }),
+            Terrain::Water
+        );
+    }
+
+    #[test]
+    fn test_from_map_rejects_ragged_rows() {
+        assert!(Board::from_map("...\n..\n").is_err());
+    }
+
+    #[test]
+    fn test_from_map_rejects_unknown_symbols() {
+        assert!(Board::from_map("..x\n...\n").is_err());
+    }
+
+    #[test]
+    fn test_from_map_rejects_more_rows_than_letters() {
+        assert!(Board::from_map(&"........\n".repeat(26)).is_ok());
+        assert!(Board::from_map(&"........\n".repeat(27)).is_err());
+    }
+
+    #[test]
+    fn test_has_room_for_fleet() {
+        let fleet = Placeable::fleet();
+        assert!(Board::new(8, 8).has_room_for(&fleet));
+        // Enough open cells, but nowhere long enough for the carrier.
+        let cramped = Board::from_map("....#....\n....#....\n....#....\n....#....\n").unwrap();
+        assert!(!cramped.has_room_for(&fleet));
+        // The ships fit, leaving a single cell for two mines.
+        let crowded = Board::from_map(".....\n.....\n.....\n..###\n").unwrap();
+        assert!(!crowded.has_room_for(&fleet));
+        assert!(Board::from_map(".....\n.....\n.....\n....#\n")
+            .unwrap()
+            .has_room_for(&fleet));
     }
 }
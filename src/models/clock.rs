@@ -0,0 +1,31 @@
+use std::time::{Duration, Instant};
+
+// Time allowed for a single turn.
+#[derive(Debug, Copy, Clone)]
+pub struct Clock {
+    limit: Duration,
+    started: Instant,
+}
+
+impl Clock {
+    pub fn new(limit: Duration) -> Clock {
+        Clock {
+            limit,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn reset(self) -> Clock {
+        Clock::new(self.limit)
+    }
+
+    pub fn remaining(&self) -> Duration {
+        self.limit
+            .checked_sub(self.started.elapsed())
+            .unwrap_or_default()
+    }
+
+    pub fn is_expired(&self) -> bool {
+        self.remaining() == Duration::from_secs(0)
+    }
+}
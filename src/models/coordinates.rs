@@ -1,4 +1,4 @@
-#[derive(Default, Debug, Copy, Clone)]
+#[derive(Default, Debug, PartialEq, Copy, Clone)]
 pub struct Coordinates {
     pub x: u16,
     pub y: u16,
@@ -32,4 +32,48 @@ impl Coordinates {
             ..self
         }
     }
+
+    // How a cell is called out on screen: a letter for the row and a
+    // number for the column, like B7.
+    pub fn name(self) -> String {
+        format!("{}{}", (b'A' + self.y as u8) as char, self.x + 1)
+    }
+
+    pub fn from_name(name: &str) -> Option<Coordinates> {
+        let mut chars = name.trim().chars();
+        let row = chars.next()?.to_ascii_uppercase();
+        if !row.is_ascii_uppercase() {
+            return None;
+        }
+        let column: u16 = chars.as_str().parse().ok()?;
+        Some(Coordinates {
+            x: column.checked_sub(1)?,
+            y: row as u16 - 'A' as u16,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_round_trips() {
+        let coordinates = Coordinates { x: 6, y: 1 };
+        assert_eq!(coordinates.name(), "B7");
+        assert_eq!(Coordinates::from_name("B7"), Some(coordinates));
+        assert_eq!(
+            Coordinates::from_name("j10"),
+            Some(Coordinates { x: 9, y: 9 })
+        );
+    }
+
+    #[test]
+    fn test_from_name_rejects_nonsense() {
+        assert_eq!(Coordinates::from_name(""), None);
+        assert_eq!(Coordinates::from_name("B"), None);
+        assert_eq!(Coordinates::from_name("B0"), None);
+        assert_eq!(Coordinates::from_name("7B"), None);
+        assert_eq!(Coordinates::from_name("Bx"), None);
+    }
 }
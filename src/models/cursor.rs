@@ -15,28 +15,24 @@ impl Cursor {
     pub fn move_up(self) -> Cursor {
         Cursor {
             origin: self.origin.get_above(),
-            ..self
         }
     }
 
     pub fn move_right(self) -> Cursor {
         Cursor {
             origin: self.origin.get_right(),
-            ..self
         }
     }
 
     pub fn move_down(self) -> Cursor {
         Cursor {
             origin: self.origin.get_below(),
-            ..self
         }
     }
 
     pub fn move_left(self) -> Cursor {
         Cursor {
             origin: self.origin.get_left(),
-            ..self
         }
     }
 }
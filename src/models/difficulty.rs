@@ -0,0 +1,59 @@
+use crate::models::Placement;
+
+// How hard the computer fleets play.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Difficulty {
+    // Fires at random and never uses special weapons.
+    Easy,
+    #[default]
+    Normal,
+    // Closes in on known hits and searches on a checkerboard.
+    Hard,
+    // Imagines every fleet that fits what it knows and fires where ships
+    // most often turn up.
+    Expert,
+}
+
+impl Difficulty {
+    pub fn all() -> Vec<Difficulty> {
+        vec![
+            Difficulty::Easy,
+            Difficulty::Normal,
+            Difficulty::Hard,
+            Difficulty::Expert,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Difficulty::Easy => "Easy",
+            Difficulty::Normal => "Normal",
+            Difficulty::Hard => "Hard",
+            Difficulty::Expert => "Expert",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Difficulty> {
+        Difficulty::all()
+            .into_iter()
+            .find(|difficulty| difficulty.name().eq_ignore_ascii_case(name))
+    }
+
+    // How a bot at this level lays out its fleet unless told otherwise.
+    pub fn placement(self) -> Placement {
+        match self {
+            Difficulty::Easy | Difficulty::Normal => Placement::Random,
+            Difficulty::Hard => Placement::Spread,
+            Difficulty::Expert => Placement::Evasive,
+        }
+    }
+
+    pub fn next(self) -> Difficulty {
+        match self {
+            Difficulty::Easy => Difficulty::Normal,
+            Difficulty::Normal => Difficulty::Hard,
+            Difficulty::Hard => Difficulty::Expert,
+            Difficulty::Expert => Difficulty::Easy,
+        }
+    }
+}
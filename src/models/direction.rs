@@ -0,0 +1,7 @@
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Direction {
+    Up,
+    Right,
+    Down,
+    Left,
+}
@@ -0,0 +1,219 @@
+use std::{
+    cell::Cell,
+    io::{BufRead, BufReader, Write},
+    process::{Child, ChildStdin, Command as Process, Stdio},
+    sync::mpsc::{channel, Receiver, RecvTimeoutError},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::models::{Board, Command, Coordinates, Faction, Placeable, Terrain};
+
+// Version of the engine protocol sent in the handshake.
+const PROTOCOL_VERSION: u32 = 1;
+
+// How long a freshly started engine gets to say it's ready.
+const HANDSHAKE_LIMIT: Duration = Duration::from_secs(5);
+
+// A bot running in a separate process, speaking the plain text protocol
+// over its stdin and stdout.
+#[derive(Debug)]
+pub struct Engine {
+    // What the engine calls itself, for the ratings.
+    pub name: String,
+    // How long it gets to answer each request.
+    time_limit: Duration,
+    child: Child,
+    stdin: ChildStdin,
+    // Lines from the engine's stdout, read on a thread of their own so
+    // waiting on them can time out.
+    replies: Receiver<String>,
+}
+
+impl Engine {
+    // Starts `command` through the shell and waits for its handshake.
+    pub fn spawn(command: &str, time_limit: Duration) -> Result<Engine, &'static str> {
+        let mut child = Process::new("sh")
+            .arg("-c")
+            .arg(command)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .spawn()
+            .map_err(|_| "Couldn't start the engine")?;
+        let stdin = child.stdin.take().unwrap();
+        let stdout = child.stdout.take().unwrap();
+        let (sender, replies) = channel();
+        thread::spawn(move || {
+            for line in BufReader::new(stdout).lines().map_while(Result::ok) {
+                if sender.send(line.trim().to_string()).is_err() {
+                    break;
+                }
+            }
+        });
+
+        let mut engine = Engine {
+            name: command.to_string(),
+            time_limit,
+            child,
+            stdin,
+            replies,
+        };
+        engine.send(&format!("rustbuckets {}", PROTOCOL_VERSION));
+        let deadline = Instant::now() + HANDSHAKE_LIMIT;
+        loop {
+            match engine.receive(deadline) {
+                Some(line) if line == "ready" => return Ok(engine),
+                Some(line) => {
+                    if let Some(name) = line.strip_prefix("name ") {
+                        // Ratings are stored tab separated.
+                        engine.name = name.split_whitespace().collect::<Vec<_>>().join(" ");
+                    }
+                }
+                None => return Err("The engine didn't finish its handshake"),
+            }
+        }
+    }
+
+    // Writes a line to the engine. One that has died simply stops
+    // answering, so there's nothing to do about errors here.
+    pub fn send(&mut self, line: &str) {
+        let _ = writeln!(self.stdin, "{}", line);
+        let _ = self.stdin.flush();
+    }
+
+    // The engine's next line, if it arrives before `deadline`.
+    fn receive(&self, deadline: Instant) -> Option<String> {
+        loop {
+            let timeout = deadline.saturating_duration_since(Instant::now());
+            match self.replies.recv_timeout(timeout) {
+                Ok(line) if line.is_empty() => continue,
+                Ok(line) => return Some(line),
+                Err(RecvTimeoutError::Timeout) | Err(RecvTimeoutError::Disconnected) => {
+                    return None
+                }
+            }
+        }
+    }
+
+    // Sends `request` and returns the commands the engine answers with,
+    // until `done` says it's answered in full.
+    fn request(
+        &mut self,
+        board: &Board,
+        request: &str,
+        done: impl Fn(&Command) -> bool,
+    ) -> Result<Vec<Command>, &'static str> {
+        // Anything still waiting was meant for an earlier request that
+        // timed out.
+        while self.replies.try_recv().is_ok() {}
+        self.send(request);
+        let deadline = Instant::now() + self.time_limit;
+        let mut commands = vec![];
+        loop {
+            let line = self.receive(deadline).ok_or("no answer in time")?;
+            let command = Command::parse(board, &line)?;
+            let finished = done(&command);
+            commands.push(command);
+            if finished {
+                return Ok(commands);
+            }
+        }
+    }
+
+    // Tells the engine which fleet it runs and what the waters look like.
+    pub fn start(&mut self, faction: Faction, players: usize, board: &Board) {
+        self.send(&format!(
+            "newgame {} {} {} {}",
+            faction.short_name(),
+            players,
+            board.width,
+            board.height
+        ));
+        for y in 0..board.height {
+            for x in 0..board.width {
+                let cell = Coordinates { x, y };
+                match board.terrain_at(&cell) {
+                    Terrain::Island => self.send(&format!("island {}", cell.name())),
+                    Terrain::Reef => self.send(&format!("reef {}", cell.name())),
+                    Terrain::Water => {}
+                }
+            }
+        }
+    }
+
+    // Asks where everything in `fleet` goes: one place command each, or
+    // auto for whatever's left.
+    pub fn place(
+        &mut self,
+        board: &Board,
+        fleet: &[Placeable],
+    ) -> Result<Vec<Command>, &'static str> {
+        let names: Vec<String> = fleet
+            .iter()
+            .map(|placeable| match placeable {
+                Placeable::Ship(length) => length.to_string(),
+                Placeable::Mine => "mine".to_string(),
+            })
+            .collect();
+        self.send(&format!("fleet {}", names.join(" ")));
+        let count = fleet.len();
+        let placed = Cell::new(0);
+        self.request(board, "place", |command| {
+            placed.set(placed.get() + 1);
+            *command == Command::Auto || placed.get() == count
+        })
+    }
+
+    // Asks for this turn's strike: a fire command, optionally preceded by
+    // a target command.
+    pub fn play(&mut self, board: &Board) -> Result<Vec<Command>, &'static str> {
+        self.request(board, "move", |command| {
+            !matches!(command, Command::Target(_))
+        })
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        self.send("quit");
+        // Give it a moment to leave on its own before pulling the plug.
+        for _ in 0..10 {
+            if let Ok(Some(_)) = self.child.try_wait() {
+                return;
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Weapon;
+
+    const ECHO_BOT: &str = "echo name Echo; echo ready; \
+        while read line; do case $line in move) echo fire radar B2;; esac; done";
+
+    #[test]
+    fn test_handshake_and_move() {
+        let mut engine = Engine::spawn(ECHO_BOT, Duration::from_secs(5)).unwrap();
+        assert_eq!(engine.name, "Echo");
+        assert_eq!(
+            engine.play(&Board::new(8, 8)),
+            Ok(vec![Command::Fire(
+                Weapon::Radar,
+                Coordinates { x: 1, y: 1 }
+            )])
+        );
+    }
+
+    #[test]
+    fn test_silent_engine_times_out() {
+        let mut engine =
+            Engine::spawn("echo ready; cat > /dev/null", Duration::from_millis(50)).unwrap();
+        assert_eq!(engine.play(&Board::new(8, 8)), Err("no answer in time"));
+    }
+}
@@ -1,11 +1,43 @@
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
 pub enum Faction {
+    #[default]
     Blue,
     Red,
+    Green,
+    Yellow,
 }
 
-impl Default for Faction {
-    fn default() -> Self {
-        Faction::Blue
+impl Faction {
+    // Factions in turn order; a game with N players uses the first N.
+    pub fn all() -> Vec<Faction> {
+        vec![Faction::Blue, Faction::Red, Faction::Green, Faction::Yellow]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Faction::Blue => "Blue Team",
+            Faction::Red => "Red Team",
+            Faction::Green => "Green Team",
+            Faction::Yellow => "Yellow Team",
+        }
+    }
+
+    // How scripts and engines refer to the faction.
+    pub fn short_name(self) -> &'static str {
+        match self {
+            Faction::Blue => "blue",
+            Faction::Red => "red",
+            Faction::Green => "green",
+            Faction::Yellow => "yellow",
+        }
+    }
+
+    // Matches "red" as well as "Red Team".
+    pub fn parse(name: &str) -> Option<Faction> {
+        Faction::all().into_iter().find(|faction| {
+            let full = faction.name();
+            full.eq_ignore_ascii_case(name)
+                || full.split(' ').next().unwrap().eq_ignore_ascii_case(name)
+        })
     }
 }
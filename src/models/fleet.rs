@@ -0,0 +1,81 @@
+use crate::models::{
+    Arsenal, Attack, AttackResult, Coordinates, Difficulty, Engine, Faction, Mine, Ping, Scores,
+    Ship,
+};
+
+// Everything belonging to one faction: its own waters and what's been done
+// to them, plus its record as a shooter.
+#[derive(Debug)]
+pub struct Fleet {
+    pub faction: Faction,
+    pub ships: Vec<Ship>,
+    pub mines: Vec<Mine>,
+    // Attacks made against this fleet's board.
+    pub attacks: Vec<Attack>,
+    // Radar sweeps made over this fleet's board.
+    pub pings: Vec<Ping>,
+    // Ship segments given away to the enemy by mine blasts.
+    pub revealed: Vec<Coordinates>,
+    pub score: Scores,
+    pub arsenal: Arsenal,
+    pub turns_lost: u16,
+    // Plays at this level rather than the game's, so bots can be pitted
+    // against each other.
+    pub difficulty: Option<Difficulty>,
+    // An external bot playing this fleet instead of the built-in AI.
+    pub engine: Option<Engine>,
+}
+
+impl Fleet {
+    pub fn new(faction: Faction) -> Fleet {
+        Fleet {
+            faction,
+            ships: vec![],
+            mines: vec![],
+            attacks: vec![],
+            pings: vec![],
+            revealed: vec![],
+            score: Scores::default(),
+            arsenal: Arsenal::default(),
+            turns_lost: 0,
+            difficulty: None,
+            engine: None,
+        }
+    }
+
+    pub fn is_hit(&self, coordinates: &Coordinates) -> bool {
+        self.attacks
+            .iter()
+            .any(|attack| attack.coordinates == *coordinates && attack.result == AttackResult::Hit)
+    }
+
+    pub fn is_ship_damaged(&self, ship: &Ship) -> bool {
+        ship.get_segment_coordinates()
+            .iter()
+            .any(|coords| self.is_hit(coords))
+    }
+
+    pub fn is_ship_sunk(&self, ship: &Ship) -> bool {
+        ship.get_segment_coordinates()
+            .iter()
+            .all(|coords| self.is_hit(coords))
+    }
+
+    // Tally of the shots that have landed in this fleet's waters.
+    pub fn damage(&self) -> Scores {
+        let hits = self
+            .attacks
+            .iter()
+            .filter(|attack| attack.result == AttackResult::Hit)
+            .count() as u16;
+        Scores {
+            hits,
+            misses: self.attacks.len() as u16 - hits,
+        }
+    }
+
+    // A fleet that has put to sea is out of the game once every ship sinks.
+    pub fn is_eliminated(&self) -> bool {
+        !self.ships.is_empty() && self.ships.iter().all(|ship| self.is_ship_sunk(ship))
+    }
+}
@@ -1,46 +1,161 @@
 use crate::{
     controllers::Mode,
-    models::{Attack, AttackResult, Coordinates, Faction, Heading, Scores, Ship},
+    models::{
+        heat_map, report, rng, Attack, AttackResult, Board, Command, Coordinates, Difficulty,
+        Direction, Faction, Fleet, GameRecord, Heading, Mine, MineEffect, Ping, Placeable,
+        Placement, Play, Profile, Ratings, Ship, ShotChart, Stats, Strike, Turn, Weapon,
+    },
 };
-use rand::{random, seq::SliceRandom, thread_rng, Rng};
-use std::{thread, time};
+use rand::{seq::SliceRandom, Rng};
+use std::time::{Duration, Instant};
 
 #[derive(Debug)]
 pub struct Game {
-    pub blue_score: Scores,
-    pub red_score: Scores,
-    pub blue_ships: Vec<Ship>,
-    pub red_ships: Vec<Ship>,
-    pub blue_attacks: Vec<Attack>,
-    pub red_attacks: Vec<Attack>,
+    // One fleet per faction, in turn order.
+    pub fleets: Vec<Fleet>,
     pub active_player: Faction,
+    // The opponent the active player is firing at this turn.
+    pub target: Faction,
     pub mode: Mode,
-    pub width: u16,
-    pub height: u16,
+    pub board: Board,
+    // Variant where a commander may reposition an undamaged ship instead
+    // of firing.
+    pub moving_fleet: bool,
+    // How long a commander gets to make each move, if there's a limit.
+    pub turn_timer: Option<Duration>,
+    // How long each AI move takes to play out on screen.
+    pub ai_delay: Duration,
+    // Every turn taken so far, oldest first.
+    pub history: Vec<Turn>,
+    // How well the computer fleets play.
+    pub difficulty: Difficulty,
+    // How many fleets an expert imagines before each shot.
+    pub samples: usize,
+    // How the computer lays out its fleet, when not left to its difficulty.
+    pub placement: Option<Placement>,
+    // Where the player has fired in past games on a board this size, by
+    // row then column. Empty when nothing's known.
+    pub player_shots: Vec<u32>,
+    // Who is playing, and how they like to play.
+    pub profile: Profile,
+    // When the shooting started.
+    pub started: Instant,
 }
 
 impl Default for Game {
     fn default() -> Self {
+        Game::new(2)
+    }
+}
+
+impl Game {
+    pub fn new(players: usize) -> Game {
+        let fleets: Vec<Fleet> = Faction::all()
+            .into_iter()
+            .take(players)
+            .map(Fleet::new)
+            .collect();
         Game {
-            blue_score: Scores::default(),
-            red_score: Scores::default(),
-            blue_ships: vec![],
-            red_ships: vec![],
-            blue_attacks: vec![],
-            red_attacks: vec![],
-            active_player: Faction::default(),
+            active_player: fleets[0].faction,
+            target: fleets[1].faction,
+            fleets,
             mode: Mode::default(),
-            width: 8,
-            height: 8,
+            board: Board::new(8, 8),
+            moving_fleet: false,
+            turn_timer: None,
+            ai_delay: Duration::from_millis(800),
+            history: vec![],
+            difficulty: Difficulty::default(),
+            samples: 1000,
+            placement: None,
+            player_shots: vec![],
+            profile: Profile::default(),
+            started: Instant::now(),
         }
     }
-}
 
-impl Game {
-    pub fn toggle_active_player(&mut self) {
-        self.active_player = match self.active_player {
-            Faction::Blue => Faction::Red,
-            Faction::Red => Faction::Blue,
+    pub fn fleet(&self, faction: Faction) -> &Fleet {
+        self.fleets
+            .iter()
+            .find(|fleet| fleet.faction == faction)
+            .expect("Faction isn't playing in this game")
+    }
+
+    pub fn fleet_mut(&mut self, faction: Faction) -> &mut Fleet {
+        self.fleets
+            .iter_mut()
+            .find(|fleet| fleet.faction == faction)
+            .expect("Faction isn't playing in this game")
+    }
+
+    fn active_fleet(&self) -> &Fleet {
+        self.fleet(self.active_player)
+    }
+
+    fn target_fleet(&self) -> &Fleet {
+        self.fleet(self.target)
+    }
+
+    // Factions still afloat, in turn order.
+    pub fn living_factions(&self) -> Vec<Faction> {
+        self.fleets
+            .iter()
+            .filter(|fleet| !fleet.is_eliminated())
+            .map(|fleet| fleet.faction)
+            .collect()
+    }
+
+    // Factions the active player may fire at.
+    pub fn opponents(&self) -> Vec<Faction> {
+        self.living_factions()
+            .into_iter()
+            .filter(|faction| *faction != self.active_player)
+            .collect()
+    }
+
+    pub fn winner(&self) -> Option<Faction> {
+        match self.living_factions().as_slice() {
+            [faction] => Some(*faction),
+            _ => None,
+        }
+    }
+
+    // A faction's place in the turn order.
+    fn seat(&self, faction: Faction) -> usize {
+        self.fleets
+            .iter()
+            .position(|fleet| fleet.faction == faction)
+            .expect("Faction isn't playing in this game")
+    }
+
+    // Factions still afloat, in turn order starting after `faction`.
+    fn living_after(&self, faction: Faction) -> Vec<Faction> {
+        let count = self.fleets.len();
+        let seat = self.seat(faction);
+        (1..count)
+            .map(|offset| &self.fleets[(seat + offset) % count])
+            .filter(|fleet| !fleet.is_eliminated())
+            .map(|fleet| fleet.faction)
+            .collect()
+    }
+
+    // Hands the turn to the next faction still afloat, aiming it at the
+    // next opponent along by default.
+    pub fn next_player(&mut self) {
+        if let Some(next) = self.living_after(self.active_player).first() {
+            self.active_player = *next;
+        }
+        if let Some(target) = self.living_after(self.active_player).first() {
+            self.target = *target;
+        }
+    }
+
+    pub fn select_target(&mut self, faction: Faction) -> Result<(), &'static str> {
+        if self.opponents().contains(&faction) {
+            self.target = faction;
+            Ok(())
+        } else {
+            Err("Can't target that faction")
         }
     }
 
@@ -49,186 +164,717 @@ impl Game {
     }
 
     pub fn increment_hits(&mut self) {
-        match self.active_player {
-            Faction::Blue => {
-                self.blue_score.hits += 1;
-            }
-            Faction::Red => {
-                self.red_score.hits += 1;
-            }
-        }
+        let active = self.active_player;
+        self.fleet_mut(active).score.hits += 1;
     }
 
     pub fn increment_misses(&mut self) {
-        match self.active_player {
-            Faction::Blue => {
-                self.blue_score.misses += 1;
+        let active = self.active_player;
+        self.fleet_mut(active).score.misses += 1;
+    }
+
+    pub fn place_ship(&mut self, ship: Ship) -> Result<(), &str> {
+        let fleet = self.active_fleet();
+        if self.should_place_ship(&fleet.ships, &fleet.mines, &ship) {
+            let active = self.active_player;
+            self.fleet_mut(active).ships.push(ship);
+            Ok(())
+        } else {
+            Err("Can't place a ship there")
+        }
+    }
+
+    pub fn place_mine(&mut self, mine: Mine) -> Result<(), &str> {
+        let fleet = self.active_fleet();
+        let coordinates = mine.coordinates;
+        if !self.board.contains(&coordinates)
+            || !self.board.terrain_at(&coordinates).is_navigable()
+            || self.is_ship_at_coordinates(&fleet.ships, &coordinates)
+            || self.is_mine_at_coordinates(&fleet.mines, &coordinates)
+        {
+            return Err("Can't place a mine there");
+        }
+
+        let active = self.active_player;
+        self.fleet_mut(active).mines.push(mine);
+        Ok(())
+    }
+
+    // Places everything in `fleet` for the active player wherever it fits.
+    pub fn auto_place_fleet(&mut self, fleet: &[Placeable]) -> Result<(), &'static str> {
+        self.place_fleet_by(fleet, Placement::Random)
+    }
+
+    fn place_fleet_by(
+        &mut self,
+        fleet: &[Placeable],
+        placement: Placement,
+    ) -> Result<(), &'static str> {
+        let active = self.active_player;
+        let (ships, mines) = (
+            self.active_fleet().ships.clone(),
+            self.active_fleet().mines.clone(),
+        );
+        if self.place_each(fleet, placement).is_ok() {
+            return Ok(());
+        }
+
+        // Picking spots one at a time can box the last ships in on a
+        // crowded board, so start over from a layout that's sure to fit.
+        let taken: Vec<Coordinates> = ships
+            .iter()
+            .flat_map(|ship| ship.get_segment_coordinates())
+            .chain(mines.iter().map(|mine| mine.coordinates))
+            .collect();
+        self.fleet_mut(active).ships = ships;
+        self.fleet_mut(active).mines = mines;
+        let layout = self
+            .board
+            .lay_out(fleet, &taken)
+            .ok_or("Couldn't fit the whole fleet on the board")?;
+        for ship in layout.into_iter() {
+            self.place_ship(ship)
+                .map_err(|_| "Couldn't fit the whole fleet on the board")?;
+        }
+        for _ in fleet
+            .iter()
+            .filter(|placeable| **placeable == Placeable::Mine)
+        {
+            let mine = (0..self.board.height)
+                .flat_map(|y| (0..self.board.width).map(move |x| Coordinates { x, y }))
+                .map(Mine::new)
+                .find(|mine| {
+                    let fleet = self.active_fleet();
+                    self.board.terrain_at(&mine.coordinates).is_navigable()
+                        && !self.is_ship_at_coordinates(&fleet.ships, &mine.coordinates)
+                        && !self.is_mine_at_coordinates(&fleet.mines, &mine.coordinates)
+                })
+                .ok_or("Couldn't fit the whole fleet on the board")?;
+            self.place_mine(mine)
+                .map_err(|_| "Couldn't fit the whole fleet on the board")?;
+        }
+        Ok(())
+    }
+
+    fn place_each(
+        &mut self,
+        fleet: &[Placeable],
+        placement: Placement,
+    ) -> Result<(), &'static str> {
+        for placeable in fleet.iter() {
+            let placed = match placeable {
+                Placeable::Ship(length) => match self.plan_ship(*length, placement) {
+                    Ok(ship) => self.place_ship(ship).is_ok(),
+                    Err(_) => false,
+                },
+                Placeable::Mine => match self.auto_create_mine() {
+                    Ok(mine) => self.place_mine(mine).is_ok(),
+                    Err(_) => false,
+                },
+            };
+            if !placed {
+                return Err("Couldn't fit the whole fleet on the board");
             }
-            Faction::Red => {
-                self.red_score.misses += 1;
+        }
+        Ok(())
+    }
+
+    // Places the active bot's whole fleet. An engine says where everything
+    // goes; whatever it leaves out or gets wrong is placed automatically.
+    pub fn place_bot_fleet(&mut self) -> Result<(), &'static str> {
+        let active = self.active_player;
+        let mut unplaced = Placeable::fleet();
+        if let Some(mut engine) = self.fleet_mut(active).engine.take() {
+            engine.start(active, self.fleets.len(), &self.board);
+            let placed = engine
+                .place(&self.board, &unplaced)
+                .and_then(|commands| self.place_commands(commands, &mut unplaced));
+            if let Err(reason) = placed {
+                engine.send(&format!("illegal {}", reason));
             }
+            self.fleet_mut(active).engine = Some(engine);
         }
+        let placement = self
+            .placement
+            .unwrap_or_else(|| self.ai_difficulty().placement());
+        self.place_fleet_by(&unplaced, placement)
     }
 
-    pub fn place_ship(&mut self, ship: Ship) -> Result<(), &str> {
-        match self.active_player {
-            Faction::Red => {
-                if self.should_place_ship(&self.red_ships, &ship) {
-                    self.red_ships.push(ship);
-                    Ok(())
-                } else {
-                    Err("Can't place a ship there")
+    // Carries out an engine's or a script's placement, crossing off what
+    // it placed.
+    pub fn place_commands(
+        &mut self,
+        commands: Vec<Command>,
+        unplaced: &mut Vec<Placeable>,
+    ) -> Result<(), &'static str> {
+        for command in commands.into_iter() {
+            let (placeable, cell, heading) = match command {
+                Command::Place(placeable, cell, heading) => (placeable, cell, heading),
+                Command::Auto => return Ok(()),
+                _ => return Err("expected place or auto"),
+            };
+            let index = unplaced
+                .iter()
+                .position(|left| *left == placeable)
+                .ok_or("nothing like that left to place")?;
+            let placed = match placeable {
+                Placeable::Ship(length) => {
+                    self.place_ship(Ship::new(cell, heading, length)).is_ok()
                 }
+                Placeable::Mine => self.place_mine(Mine::new(cell)).is_ok(),
+            };
+            if !placed {
+                return Err("doesn't fit there");
             }
-            Faction::Blue => {
-                if self.should_place_ship(&self.blue_ships, &ship) {
-                    self.blue_ships.push(ship);
-                    Ok(())
-                } else {
-                    Err("Can't place a ship there")
+            unplaced.remove(index);
+        }
+        Ok(())
+    }
+
+    pub fn auto_create_mine(&self) -> Result<Mine, &str> {
+        for _ in 0..self.board.width * self.board.height {
+            if let Ok(origin) = self.auto_select_origin() {
+                if !self.is_mine_at_coordinates(&self.active_fleet().mines, &origin) {
+                    return Ok(Mine::new(origin));
                 }
             }
         }
+        Err("Couldn't place a mine anywhere")
     }
 
     pub fn auto_select_origin(&self) -> Result<Coordinates, &str> {
-        for _ in 0..self.width * self.height {
-            let mut rng = thread_rng();
+        for _ in 0..self.board.width * self.board.height {
+            let mut rng = rng();
             let origin = Coordinates {
-                x: rng.gen_range(0, self.width),
-                y: rng.gen_range(0, self.height),
+                x: rng.gen_range(0, self.board.width),
+                y: rng.gen_range(0, self.board.height),
             };
 
-            match self.active_player {
-                Faction::Red => {
-                    if !self.is_ship_at_coordinates(&self.red_ships, &origin) {
-                        return Ok(origin);
-                    }
-                }
-                Faction::Blue => {
-                    if !self.is_ship_at_coordinates(&self.blue_ships, &origin) {
-                        return Ok(origin);
-                    }
-                }
+            if !self.board.terrain_at(&origin).is_navigable() {
+                continue;
+            }
+
+            if !self.is_ship_at_coordinates(&self.active_fleet().ships, &origin) {
+                return Ok(origin);
             }
         }
         Err("No legal origin!")
     }
 
     fn auto_select_heading(&self, origin: Coordinates, length: u16) -> Result<Heading, &str> {
-        match self.active_player {
-            Faction::Red => {
-                let heading = random();
-                let tentative_ship = Ship::new(origin, heading, length);
-                if self.should_place_ship(&self.red_ships, &tentative_ship) {
-                    return Ok(heading);
-                } else {
-                    let heading = heading.flip();
-                    let tentative_ship = Ship::new(origin, heading, length);
-                    if self.should_place_ship(&self.red_ships, &tentative_ship) {
-                        return Ok(heading);
-                    } else {
-                        return Err("Couldn't find a good heading");
-                    }
-                }
-            }
-            Faction::Blue => {
-                let heading = random();
-                let tentative_ship = Ship::new(origin, heading, length);
-                if self.should_place_ship(&self.blue_ships, &tentative_ship) {
-                    return Ok(heading);
-                } else {
-                    let heading = heading.flip();
-                    let tentative_ship = Ship::new(origin, heading, length);
-                    if self.should_place_ship(&self.blue_ships, &tentative_ship) {
-                        return Ok(heading);
-                    } else {
-                        return Err("Couldn't find a good heading");
-                    }
-                }
-            }
+        let fleet = self.active_fleet();
+
+        let heading = rng().gen();
+        let tentative_ship = Ship::new(origin, heading, length);
+        if self.should_place_ship(&fleet.ships, &fleet.mines, &tentative_ship) {
+            return Ok(heading);
+        }
+
+        let heading = heading.flip();
+        let tentative_ship = Ship::new(origin, heading, length);
+        if self.should_place_ship(&fleet.ships, &fleet.mines, &tentative_ship) {
+            Ok(heading)
+        } else {
+            Err("Couldn't find a good heading")
         }
     }
 
     pub fn auto_create_ship(&self, length: u16) -> Result<Ship, &str> {
-        for _ in 0..self.width * self.height {
+        for _ in 0..self.board.width * self.board.height {
             // Create an origin
             // Any origin that is on the board and isn't occupied is legal.
-            let origin = self.auto_select_origin();
-            if origin.is_ok() {
-                let heading = self.auto_select_heading(origin.unwrap(), length);
-                if heading.is_ok() {
-                    return Ok(Ship::new(origin.unwrap(), heading.unwrap(), length));
+            if let Ok(origin) = self.auto_select_origin() {
+                if let Ok(heading) = self.auto_select_heading(origin, length) {
+                    return Ok(Ship::new(origin, heading, length));
                 }
             }
         }
         Err("Couldn't place a ship anywhere")
     }
 
-    fn should_place_attack(&self, attacks: &Vec<Attack>, coordinates: &Coordinates) -> bool {
+    // Picks a spot for the active player's next ship the way `placement`
+    // would.
+    pub fn plan_ship(&self, length: u16, placement: Placement) -> Result<Ship, &str> {
+        if placement == Placement::Random {
+            return self.auto_create_ship(length);
+        }
+        let fleet = self.active_fleet();
+        let mut candidates = vec![];
+        for y in 0..self.board.height {
+            for x in 0..self.board.width {
+                for heading in [Heading::East, Heading::South].iter() {
+                    let ship = Ship::new(Coordinates { x, y }, *heading, length);
+                    if self.should_place_ship(&fleet.ships, &fleet.mines, &ship) {
+                        candidates.push(ship);
+                    }
+                }
+            }
+        }
+        placement
+            .choose(&self.board, &fleet.ships, &candidates, &self.player_shots)
+            .ok_or("Couldn't place a ship anywhere")
+    }
+
+    fn should_place_attack(&self, attacks: &[Attack], coordinates: &Coordinates) -> bool {
         for attack in attacks.iter() {
-            if attack.coordinates.x == coordinates.x && attack.coordinates.y == coordinates.y {
+            if attack.coordinates.x == coordinates.x
+                && attack.coordinates.y == coordinates.y
+                && !attack.stale
+            {
                 return false;
             }
         }
-        return true;
+        true
     }
 
-    pub fn place_attack(&mut self, coordinates: Coordinates) -> Result<Attack, &str> {
-        match self.active_player {
-            Faction::Red => {
-                if self.should_place_attack(&self.blue_attacks, &coordinates) {
-                    let attack = Attack::new(&self.blue_ships, coordinates);
-                    match attack.result {
-                        AttackResult::Hit => {
-                            self.increment_hits();
-                        }
-                        AttackResult::Miss => {
-                            self.increment_misses();
+    fn resolve_attack(&self, coordinates: Coordinates) -> Attack {
+        let target = self.target_fleet();
+        // A shot that lands on an island is wasted.
+        if self.board.terrain_at(&coordinates).blocks_fire() {
+            return Attack::with_result(coordinates, AttackResult::Miss);
+        }
+        if self.is_mine_at_coordinates(&target.mines, &coordinates) {
+            return Attack::with_result(coordinates, AttackResult::Mine(self.roll_mine_effect()));
+        }
+        Attack::new(&target.ships, coordinates)
+    }
+
+    fn roll_mine_effect(&self) -> MineEffect {
+        // The blast gives away an intact, not yet revealed segment of the
+        // shooter's own fleet, or failing that costs them a turn.
+        let shooter = self.active_fleet();
+        let hidden: Vec<Coordinates> = shooter
+            .ships
+            .iter()
+            .flat_map(|ship| ship.get_segment_coordinates())
+            .filter(|coords| self.should_place_attack(&shooter.attacks, coords))
+            .filter(|coords| !shooter.revealed.contains(coords))
+            .collect();
+
+        let mut rng = rng();
+        match hidden.choose(&mut rng) {
+            Some(coords) if rng.gen() => MineEffect::Reveal(*coords),
+            _ => MineEffect::LoseTurn,
+        }
+    }
+
+    fn apply_mine_effect(&mut self, coordinates: Coordinates, effect: MineEffect) {
+        let (active, target) = (self.active_player, self.target);
+        self.fleet_mut(target)
+            .mines
+            .retain(|mine| mine.coordinates != coordinates);
+        let shooter = self.fleet_mut(active);
+        match effect {
+            MineEffect::Reveal(coords) => shooter.revealed.push(coords),
+            MineEffect::LoseTurn => shooter.turns_lost += 1,
+        }
+    }
+
+    // Returns true, and uses up the penalty, if the active player has a
+    // turn to sit out.
+    pub fn consume_lost_turn(&mut self) -> bool {
+        let active = self.active_player;
+        let turns_lost = &mut self.fleet_mut(active).turns_lost;
+        if *turns_lost > 0 {
+            *turns_lost -= 1;
+            self.history.push(Turn::new(active, Play::LostTurn));
+            self.tell_engines();
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn place_attack(&mut self, coordinates: Coordinates) -> Result<Attack, &'static str> {
+        if !self.should_attack_cell(&coordinates) {
+            return Err("Can't place an attack there");
+        }
+
+        let attack = self.resolve_attack(coordinates);
+        match attack.result {
+            AttackResult::Hit => self.increment_hits(),
+            AttackResult::Miss => self.increment_misses(),
+            AttackResult::Mine(effect) => {
+                self.increment_misses();
+                self.apply_mine_effect(coordinates, effect);
+            }
+        };
+        let target = self.target;
+        let attacks = &mut self.fleet_mut(target).attacks;
+        attacks.retain(|previous| previous.coordinates != coordinates);
+        attacks.push(attack);
+        Ok(attack)
+    }
+
+    // Why the active fleet can't fire `weapon` at `coordinates` right now,
+    // if it can't. Anything that passes is sure to launch.
+    fn check_strike(&self, weapon: Weapon, coordinates: Coordinates) -> Result<(), &'static str> {
+        if !self.board.contains(&coordinates) {
+            return Err("That's off the board");
+        }
+        if !self.active_fleet().arsenal.has(weapon) {
+            return Err("You're out of that weapon");
+        }
+        match weapon {
+            Weapon::Shot if !self.should_attack_cell(&coordinates) => {
+                Err("You've already fired there")
+            }
+            Weapon::Airstrike if self.airstrike_cells(coordinates).is_empty() => {
+                Err("There's nothing left to hit there")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    // The cells an airstrike at `coordinates` would hit: it and its
+    // neighbours on the row, leaving out any already fired at.
+    fn airstrike_cells(&self, coordinates: Coordinates) -> Vec<Coordinates> {
+        (coordinates.x.saturating_sub(1)..=coordinates.x + 1)
+            .map(|x| Coordinates { x, ..coordinates })
+            .filter(|coords| self.board.contains(coords) && self.should_attack_cell(coords))
+            .collect()
+    }
+
+    pub fn launch(&mut self, weapon: Weapon, coordinates: Coordinates) -> Result<Strike, &str> {
+        self.check_strike(weapon, coordinates)?;
+
+        let strike = match weapon {
+            Weapon::Shot => Strike::Attacks(vec![self.place_attack(coordinates)?]),
+            Weapon::Radar => {
+                let mut ping = Ping::new(coordinates, false);
+                ping.found = self
+                    .target_fleet()
+                    .ships
+                    .iter()
+                    .flat_map(|ship| ship.get_segment_coordinates())
+                    .any(|coords| ping.covers(&coords));
+                let target = self.target;
+                self.fleet_mut(target).pings.push(ping);
+                Strike::Ping(ping)
+            }
+            Weapon::Airstrike => {
+                let mut attacks = vec![];
+                for coords in self.airstrike_cells(coordinates).into_iter() {
+                    attacks.push(self.place_attack(coords)?);
+                }
+                Strike::Attacks(attacks)
+            }
+            Weapon::Torpedo => {
+                let mut attacks = vec![];
+                for x in coordinates.x..self.board.width {
+                    let coords = Coordinates { x, ..coordinates };
+                    let target = self.target_fleet();
+                    let previous = target
+                        .attacks
+                        .iter()
+                        .find(|attack| attack.coordinates == coords && !attack.stale)
+                        .map(|attack| attack.result);
+                    let solid = self.board.terrain_at(&coords).blocks_fire()
+                        || self.is_ship_at_coordinates(&target.ships, &coords)
+                        || self.is_mine_at_coordinates(&target.mines, &coords);
+
+                    match previous {
+                        // Open water we already know about; keep running.
+                        None if !solid => continue,
+                        Some(AttackResult::Miss) if !solid => continue,
+                        Some(AttackResult::Mine(_)) => continue,
+                        None => {
+                            attacks.push(self.place_attack(coords)?);
+                            break;
                         }
-                    };
-                    self.blue_attacks.push(attack);
-                    Ok(attack.clone())
-                } else {
-                    Err("Can't place an attack there")
+                        // The torpedo slams into a wreck or an island we
+                        // already shelled.
+                        Some(_) => break,
+                    }
                 }
+                Strike::Attacks(attacks)
             }
-            Faction::Blue => {
-                if self.should_place_attack(&self.red_attacks, &coordinates) {
-                    let attack = Attack::new(&self.red_ships, coordinates);
-                    match attack.result {
-                        AttackResult::Hit => self.increment_hits(),
-                        AttackResult::Miss => self.increment_misses(),
-                    };
-                    self.red_attacks.push(attack);
-                    Ok(attack.clone())
-                } else {
-                    Err("Can't place an attack there")
+        };
+
+        let active = self.active_player;
+        self.fleet_mut(active).arsenal.spend(weapon);
+        self.history.push(Turn::new(
+            active,
+            Play::Fire {
+                weapon,
+                target: self.target,
+                strike: strike.clone(),
+            },
+        ));
+        self.tell_engines();
+        Ok(strike)
+    }
+
+    fn should_attack_cell(&self, coordinates: &Coordinates) -> bool {
+        self.should_place_attack(&self.target_fleet().attacks, coordinates)
+    }
+
+    // Indices of the active player's ships that are allowed to move.
+    pub fn movable_ships(&self) -> Vec<usize> {
+        if !self.moving_fleet {
+            return vec![];
+        }
+        let fleet = self.active_fleet();
+        (0..fleet.ships.len())
+            .filter(|index| !fleet.is_ship_damaged(&fleet.ships[*index]))
+            .collect()
+    }
+
+    // Where `ship` would end up moving one cell in `direction`, if it
+    // stays on the board and clear of the rest of the fleet.
+    fn plan_ship_move(&self, index: usize, direction: Direction) -> Option<Ship> {
+        let fleet = self.active_fleet();
+        let ship = fleet.ships[index];
+        let moved = match direction {
+            Direction::Up if ship.origin.y > 0 => ship.move_up(),
+            Direction::Left if ship.origin.x > 0 => ship.move_left(),
+            Direction::Down => ship.move_down(),
+            Direction::Right => ship.move_right(),
+            _ => return None,
+        };
+        let mut others = fleet.ships.clone();
+        others.remove(index);
+        if self.should_place_ship(&others, &fleet.mines, &moved) {
+            Some(moved)
+        } else {
+            None
+        }
+    }
+
+    pub fn move_ship(&mut self, index: usize, direction: Direction) -> Result<(), &'static str> {
+        if !self.moving_fleet {
+            return Err("Ships can't move in this game");
+        }
+        let fleet = self.active_fleet();
+        let ship = match fleet.ships.get(index) {
+            Some(ship) => *ship,
+            None => return Err("There's no such ship"),
+        };
+        if fleet.is_ship_damaged(&ship) {
+            return Err("Damaged ships can't move");
+        }
+        let moved = match self.plan_ship_move(index, direction) {
+            Some(moved) => moved,
+            None => return Err("Can't move a ship there"),
+        };
+
+        // Whatever the enemy learned about these waters may now be wrong.
+        let old_segments = ship.get_segment_coordinates();
+        let active = self.active_player;
+        let fleet = self.fleet_mut(active);
+        fleet.ships[index] = moved;
+        fleet
+            .revealed
+            .retain(|coords| !old_segments.contains(coords));
+        fleet.pings.clear();
+        // Misses and spent mines both leave open water a ship may now be
+        // sitting in.
+        for attack in fleet.attacks.iter_mut() {
+            if matches!(attack.result, AttackResult::Miss | AttackResult::Mine(_)) {
+                attack.stale = true;
+            }
+        }
+        self.history.push(Turn::new(active, Play::Move));
+        self.tell_engines();
+        Ok(())
+    }
+
+    pub fn auto_plan_move(&self) -> Option<(usize, Direction)> {
+        if !self.moving_fleet {
+            return None;
+        }
+
+        // Only bother moving a ship the enemy seems to be closing in on.
+        let fleet = self.active_fleet();
+        let mut rng = rng();
+        for index in self.movable_ships().into_iter() {
+            let threatened = fleet.ships[index]
+                .get_segment_coordinates()
+                .iter()
+                .any(|coords| {
+                    fleet.revealed.contains(coords)
+                        || fleet
+                            .pings
+                            .iter()
+                            .any(|ping| ping.found && ping.covers(coords))
+                });
+            if !threatened {
+                continue;
+            }
+
+            let mut directions = vec![
+                Direction::Up,
+                Direction::Right,
+                Direction::Down,
+                Direction::Left,
+            ];
+            directions.shuffle(&mut rng);
+            for direction in directions.into_iter() {
+                if self.plan_ship_move(index, direction).is_some() {
+                    return Some((index, direction));
                 }
             }
         }
+        None
     }
 
-    pub fn think() {
-        // Pause for a period of time to simulate thought.
-        let duration = time::Duration::from_millis(1500);
-        thread::sleep(duration);
+    // Picks who the AI should fire at: whoever it has the most leads on,
+    // and failing that whoever is closest to sinking.
+    pub fn auto_plan_target(&self) -> Faction {
+        let mut best = self.target;
+        let mut best_score = (0, 0);
+        for faction in self.opponents().into_iter() {
+            let fleet = self.fleet(faction);
+            let leads = self.auto_find_leads_against(fleet).len();
+            let hits = fleet
+                .attacks
+                .iter()
+                .filter(|attack| attack.result == AttackResult::Hit)
+                .count();
+            if (leads, hits) > best_score || !self.opponents().contains(&best) {
+                best = faction;
+                best_score = (leads, hits);
+            }
+        }
+        best
     }
 
-    pub fn auto_plan_attack(&self) -> Result<Coordinates, &str> {
+    // Cells worth following up on before searching blind: ship segments a
+    // mine blast gave away, then anything inside a radar contact.
+    fn auto_find_leads(&self) -> Vec<Coordinates> {
+        self.auto_find_leads_against(self.target_fleet())
+    }
+
+    fn auto_find_leads_against(&self, target: &Fleet) -> Vec<Coordinates> {
+        // An easy opponent doesn't follow up on anything.
+        if self.ai_difficulty() == Difficulty::Easy {
+            return vec![];
+        }
+
+        let revealed = target
+            .revealed
+            .iter()
+            .filter(|coords| self.should_place_attack(&target.attacks, coords))
+            .copied();
+        // A hard one closes in around every hit.
+        let mut around_hits: Vec<Coordinates> = vec![];
+        if matches!(self.ai_difficulty(), Difficulty::Hard | Difficulty::Expert) {
+            for attack in target.attacks.iter() {
+                if attack.result != AttackResult::Hit {
+                    continue;
+                }
+                let Coordinates { x, y } = attack.coordinates;
+                let mut neighbours = vec![Coordinates { x: x + 1, y }, Coordinates { x, y: y + 1 }];
+                if x > 0 {
+                    neighbours.push(Coordinates { x: x - 1, y });
+                }
+                if y > 0 {
+                    neighbours.push(Coordinates { x, y: y - 1 });
+                }
+                around_hits.extend(neighbours.into_iter().filter(|coords| {
+                    self.board.contains(coords)
+                        && self.board.terrain_at(coords).is_navigable()
+                        && self.should_place_attack(&target.attacks, coords)
+                }));
+            }
+            around_hits.shuffle(&mut rng());
+        }
+        let mut contacts: Vec<Coordinates> = self
+            .auto_find_candidates_against(target)
+            .into_iter()
+            .filter(|coords| {
+                target
+                    .pings
+                    .iter()
+                    .any(|ping| ping.found && ping.covers(coords))
+            })
+            .collect();
+        contacts.shuffle(&mut rng());
+
+        let mut leads: Vec<Coordinates> = revealed.collect();
+        for coords in around_hits.into_iter().chain(contacts) {
+            if !leads.contains(&coords) {
+                leads.push(coords);
+            }
+        }
+        leads
+    }
+
+    fn auto_find_candidates(&self) -> Vec<Coordinates> {
+        self.auto_find_candidates_against(self.target_fleet())
+    }
+
+    fn auto_find_candidates_against(&self, target: &Fleet) -> Vec<Coordinates> {
         let mut all_possible_coords: Vec<Coordinates> = vec![];
-        for x in 0..8 {
-            for y in 0..8 {
+        for x in 0..self.board.width {
+            for y in 0..self.board.height {
                 all_possible_coords.push(Coordinates { x, y });
             }
         }
 
-        let mut filtered_coords: Vec<Coordinates> = all_possible_coords
+        // The terrain is public, so there's no point shooting at a cell
+        // that could never hold a ship. Nor at one a radar sweep cleared.
+        all_possible_coords
             .into_iter()
-            .filter(|coords| self.should_place_attack(&self.blue_attacks, &coords))
-            .collect();
+            .filter(|coords| self.board.terrain_at(coords).is_navigable())
+            .filter(|coords| self.should_place_attack(&target.attacks, coords))
+            .filter(|coords| {
+                !target
+                    .pings
+                    .iter()
+                    .any(|ping| !ping.found && ping.covers(coords))
+            })
+            .collect()
+    }
+
+    // The expert's pick: whichever open cell holds a ship in the most
+    // imagined fleets that fit everything known about the target.
+    fn auto_sample_attack(&self) -> Option<Coordinates> {
+        let counts = heat_map(&self.board, self.target_fleet(), self.samples)?;
+        let width = usize::from(self.board.width);
+        let mut candidates = self.auto_find_candidates();
+        // Shuffled so ties are broken at random.
+        candidates.shuffle(&mut rng());
+        candidates
+            .into_iter()
+            .map(|coords| {
+                (
+                    counts[usize::from(coords.y) * width + usize::from(coords.x)],
+                    coords,
+                )
+            })
+            .filter(|(count, _)| *count > 0)
+            .max_by_key(|(count, _)| *count)
+            .map(|(_, coords)| coords)
+    }
 
-        filtered_coords.shuffle(&mut thread_rng());
+    pub fn auto_plan_attack(&self) -> Result<Coordinates, &str> {
+        // Should no imagined fleet fit what the expert knows, it searches
+        // like a hard opponent.
+        if self.ai_difficulty() == Difficulty::Expert {
+            if let Some(coords) = self.auto_sample_attack() {
+                return Ok(coords);
+            }
+        }
+        if let Some(coords) = self.auto_find_leads().first() {
+            return Ok(*coords);
+        }
+
+        // Cells we've never shot at come first; misses gone stale after
+        // the enemy moved a ship are only worth revisiting after that.
+        let attacks = &self.target_fleet().attacks;
+        let (mut filtered_coords, mut stale_coords): (Vec<Coordinates>, Vec<Coordinates>) = self
+            .auto_find_candidates()
+            .into_iter()
+            .partition(|coords| !attacks.iter().any(|attack| attack.coordinates == *coords));
+        stale_coords.shuffle(&mut rng());
+        filtered_coords.shuffle(&mut rng());
+        if matches!(self.ai_difficulty(), Difficulty::Hard | Difficulty::Expert) {
+            // Every ship covers at least two cells, so searching one
+            // colour of a checkerboard is enough to find them all.
+            filtered_coords.sort_by_key(|coords| (coords.x + coords.y) % 2 == 0);
+        }
+        filtered_coords.splice(0..0, stale_coords);
 
         match filtered_coords.pop() {
             Some(coords) => Ok(coords),
@@ -236,32 +882,256 @@ impl Game {
         }
     }
 
-    pub fn kiss_ling_ling(&self) {
-        println!("{}{}{}", "\u{1F436}", "\u{1F48B}", "\u{1F407}",);
+    fn ai_difficulty(&self) -> Difficulty {
+        self.active_fleet().difficulty.unwrap_or(self.difficulty)
     }
 
-    fn should_place_ship(&self, ships: &Vec<Ship>, ship: &Ship) -> bool {
-        for coordinates in ship.get_segment_coordinates().iter() {
-            if self.is_ship_at_coordinates(&ships, coordinates) {
-                return false;
+    // What to call a faction on screen: the player goes by their profile
+    // name.
+    pub fn faction_name(&self, faction: Faction) -> String {
+        match faction {
+            Faction::Blue => self.profile.name.clone(),
+            faction => faction.name().to_string(),
+        }
+    }
+
+    // Who a faction's results count towards in the ratings: the player,
+    // or whichever bot is running the fleet.
+    pub fn rating_name(&self, faction: Faction) -> String {
+        if let Some(engine) = &self.fleet(faction).engine {
+            return format!("Engine ({})", engine.name);
+        }
+        match self.fleet(faction).difficulty {
+            None if faction == Faction::Blue => self.profile.name.clone(),
+            difficulty => format!("Bot ({})", difficulty.unwrap_or(self.difficulty).name()),
+        }
+    }
+
+    // Settles the game in `ratings`. The winner is whoever is left afloat;
+    // a game that ends with several different commanders still fighting
+    // doesn't count.
+    pub fn rate(&self, ratings: &mut Ratings) -> bool {
+        let mut survivors: Vec<String> = self
+            .living_factions()
+            .into_iter()
+            .map(|faction| self.rating_name(faction))
+            .collect();
+        survivors.sort();
+        survivors.dedup();
+        if survivors.len() != 1 {
+            return false;
+        }
+        // Someone who lost several fleets only lost the game once.
+        let mut losers: Vec<String> = self
+            .fleets
+            .iter()
+            .filter(|fleet| fleet.is_eliminated())
+            .map(|fleet| self.rating_name(fleet.faction))
+            .collect();
+        losers.sort();
+        losers.dedup();
+        ratings.record(&survivors[0], &losers);
+        true
+    }
+
+    // Adds where the player fired this game to their chart, so the
+    // computer can hide from them next time.
+    pub fn chart_shots(&self, chart: &mut ShotChart) {
+        chart.record(
+            &self.profile.name,
+            (self.board.width, self.board.height),
+            &self.history,
+            Faction::Blue,
+        );
+    }
+
+    // Asks the active fleet's engine, if it has one, what to fire where,
+    // aiming at whichever fleet it picks. Anything illegal or late is
+    // reported back to the engine and the built-in AI's choice used
+    // instead.
+    pub fn engine_plan_strike(&mut self) -> Option<(Weapon, Coordinates)> {
+        let active = self.active_player;
+        let mut engine = self.fleet_mut(active).engine.take()?;
+        let plan = match engine
+            .play(&self.board)
+            .and_then(|commands| self.check_engine_strike(commands))
+        {
+            Ok(plan) => Some(plan),
+            Err(reason) => {
+                engine.send(&format!("illegal {}", reason.to_lowercase()));
+                let target = self.auto_plan_target();
+                let _ = self.select_target(target);
+                self.auto_plan_strike().ok()
+            }
+        };
+        self.fleet_mut(active).engine = Some(engine);
+        plan
+    }
+
+    fn check_engine_strike(
+        &mut self,
+        commands: Vec<Command>,
+    ) -> Result<(Weapon, Coordinates), &'static str> {
+        for command in commands.into_iter() {
+            match command {
+                Command::Target(faction) => self.select_target(faction)?,
+                // Turned down here, so the engine hears why rather than
+                // the launch quietly failing later.
+                Command::Fire(weapon, cell) => {
+                    self.check_strike(weapon, cell)?;
+                    return Ok((weapon, cell));
+                }
+                _ => break,
+            }
+        }
+        Err("expected a fire command")
+    }
+
+    // Lets every engine in the game know what just happened, and who won
+    // if that settled it.
+    fn tell_engines(&mut self) {
+        if self.fleets.iter().all(|fleet| fleet.engine.is_none()) {
+            return;
+        }
+        let mut lines = match self.history.last() {
+            Some(turn) => report(self, turn),
+            None => vec![],
+        };
+        if let Some(winner) = self.winner() {
+            lines.push(format!("gameover {}", winner.short_name()));
+        }
+        for engine in self
+            .fleets
+            .iter_mut()
+            .filter_map(|fleet| fleet.engine.as_mut())
+        {
+            for line in lines.iter() {
+                engine.send(line);
+            }
+        }
+    }
+
+    // Plays the active AI fleet's whole turn in one go, for games nobody
+    // is watching.
+    pub fn auto_play_turn(&mut self) -> Result<(), &'static str> {
+        if self.consume_lost_turn() {
+            return Ok(());
+        }
+        if let Some((weapon, coordinates)) = self.engine_plan_strike() {
+            if self.launch(weapon, coordinates).is_ok() {
+                return Ok(());
+            }
+        } else if let Some((index, direction)) = self.auto_plan_move() {
+            return self.move_ship(index, direction);
+        }
+        let target = self.auto_plan_target();
+        self.select_target(target)?;
+        loop {
+            let (weapon, coordinates) = match self.auto_plan_strike() {
+                Ok(plan) => plan,
+                Err(_) => return Err("There's nothing left to fire at"),
+            };
+            if self.launch(weapon, coordinates).is_ok() {
+                return Ok(());
             }
         }
+    }
+
+    // How the game went for the player.
+    pub fn record(&self) -> GameRecord {
+        let stats = Stats::new(&self.history, Faction::Blue);
+        GameRecord {
+            profile: self.profile.name.clone(),
+            won: self.winner() == Some(Faction::Blue),
+            difficulty: self.difficulty,
+            shots: stats.shots,
+            hits: stats.hits,
+            turns: stats.turns,
+            duration: self.started.elapsed(),
+        }
+    }
+
+    // Any cell on the target's board that can still be fired at, for when
+    // a commander runs out of time to choose.
+    pub fn random_attack(&self) -> Result<Coordinates, &str> {
+        let mut cells: Vec<Coordinates> = vec![];
+        for x in 0..self.board.width {
+            for y in 0..self.board.height {
+                let coords = Coordinates { x, y };
+                if self.should_attack_cell(&coords) {
+                    cells.push(coords);
+                }
+            }
+        }
+        match cells.choose(&mut rng()) {
+            Some(coords) => Ok(*coords),
+            None => Err("There's nothing left to fire at!"),
+        }
+    }
+
+    pub fn auto_plan_strike(&self) -> Result<(Weapon, Coordinates), &str> {
+        let target = self.auto_plan_attack()?;
+        // An expert trusts its sums over a gamble.
+        if matches!(self.ai_difficulty(), Difficulty::Easy | Difficulty::Expert)
+            || !self.auto_find_leads().is_empty()
+        {
+            return Ok((Weapon::Shot, target));
+        }
+
+        // With nothing to follow up on, now and then gamble on a special
+        // weapon to speed up the search.
+        let mut rng = rng();
+        let arsenal = self.active_fleet().arsenal;
+        for (weapon, odds) in [
+            (Weapon::Radar, 4),
+            (Weapon::Airstrike, 6),
+            (Weapon::Torpedo, 6),
+        ]
+        .iter()
+        {
+            if arsenal.has(*weapon) && rng.gen_ratio(1, *odds) {
+                return Ok((*weapon, target));
+            }
+        }
+        Ok((Weapon::Shot, target))
+    }
+
+    #[allow(dead_code)]
+    pub fn kiss_ling_ling(&self) {
+        println!("\u{1F436}\u{1F48B}\u{1F407}");
+    }
+
+    fn should_place_ship(&self, ships: &[Ship], mines: &[Mine], ship: &Ship) -> bool {
+        if !self.board.contains(&ship.origin) {
+            return false;
+        }
         match ship.heading {
             Heading::East => {
-                if self.width - ship.origin.x < ship.length {
+                if self.board.width - ship.origin.x < ship.length {
                     return false;
                 }
             }
             Heading::South => {
-                if self.height - ship.origin.y < ship.length {
+                if self.board.height - ship.origin.y < ship.length {
                     return false;
                 }
             }
         }
+        for coordinates in ship.get_segment_coordinates().iter() {
+            if !self.board.terrain_at(coordinates).is_navigable() {
+                return false;
+            }
+            if self.is_ship_at_coordinates(ships, coordinates) {
+                return false;
+            }
+            if self.is_mine_at_coordinates(mines, coordinates) {
+                return false;
+            }
+        }
         true
     }
 
-    fn is_ship_at_coordinates(&self, ships: &Vec<Ship>, coordinates: &Coordinates) -> bool {
+    fn is_ship_at_coordinates(&self, ships: &[Ship], coordinates: &Coordinates) -> bool {
         for ship in ships.iter() {
             for coords in ship.get_segment_coordinates().iter() {
                 if coords.x == coordinates.x && coords.y == coordinates.y {
@@ -271,6 +1141,10 @@ impl Game {
         }
         false
     }
+
+    fn is_mine_at_coordinates(&self, mines: &[Mine], coordinates: &Coordinates) -> bool {
+        mines.iter().any(|mine| mine.coordinates == *coordinates)
+    }
 }
 
 #[cfg(test)]
@@ -278,10 +1152,10 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_toggle_active_player() {
+    fn test_next_player() {
         let mut game = Game::default();
         assert_eq!(game.active_player, Faction::Blue);
-        game.toggle_active_player();
+        game.next_player();
         assert_eq!(game.active_player, Faction::Red);
     }
 
@@ -296,27 +1170,27 @@ mod tests {
     #[test]
     fn test_place_ship_empty_board() {
         let mut game = Game::default();
-        assert_eq!(game.blue_ships.len(), 0);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
         let result = game.place_ship(Ship::default());
         assert!(result.is_ok());
-        assert_eq!(game.blue_ships.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
     }
 
     #[test]
     fn test_place_ship_should_not_share_origin() {
         let mut game = Game::default();
-        assert_eq!(game.blue_ships.len(), 0);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
         game.place_ship(Ship::default()).unwrap();
-        assert_eq!(game.blue_ships.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
         let result = game.place_ship(Ship::default());
         assert!(result.is_err());
-        assert_eq!(game.blue_ships.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
     }
 
     #[test]
     fn test_ships_should_not_go_off_board() {
         let mut game = Game::default();
-        assert_eq!(game.blue_ships.len(), 0);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
         let result = game.place_ship(Ship::new(Coordinates { x: 7, y: 0 }, Heading::East, 2));
         assert!(result.is_err());
     }
@@ -324,55 +1198,59 @@ mod tests {
     #[test]
     fn test_ships_should_not_overlap_with_other_ships() {
         let mut game = Game::default();
-        assert_eq!(game.blue_ships.len(), 0);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
         game.place_ship(Ship::default()).unwrap();
-        assert_eq!(game.blue_ships.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
         let result = game.place_ship(Ship::default());
         assert!(result.is_err());
-        assert_eq!(game.blue_ships.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
     }
 
     #[test]
     fn test_new_attack_hit() {
         let mut game = Game::default();
         game.place_ship(Ship::default()).unwrap();
-        assert_eq!(game.blue_ships.len(), 1);
-        game.toggle_active_player();
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
+        game.next_player();
         game.place_attack(Coordinates { x: 0, y: 0 }).unwrap();
-        assert_eq!(game.blue_attacks.len(), 1);
-        assert_eq!(game.blue_attacks[0].result, AttackResult::Hit);
+        assert_eq!(game.fleet(Faction::Blue).attacks.len(), 1);
+        assert_eq!(
+            game.fleet(Faction::Blue).attacks[0].result,
+            AttackResult::Hit
+        );
     }
 
     #[test]
     fn test_new_attack_miss() {
         let mut game = Game::default();
         game.place_ship(Ship::default()).unwrap();
-        assert_eq!(game.blue_ships.len(), 1);
-        game.toggle_active_player();
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
+        game.next_player();
         game.place_attack(Coordinates { x: 2, y: 2 }).unwrap();
-        assert_eq!(game.blue_attacks.len(), 1);
-        assert_eq!(game.blue_attacks[0].result, AttackResult::Miss);
+        assert_eq!(game.fleet(Faction::Blue).attacks.len(), 1);
+        assert_eq!(
+            game.fleet(Faction::Blue).attacks[0].result,
+            AttackResult::Miss
+        );
     }
 
     #[test]
     fn test_new_attack_already_made() {
         let mut game = Game::default();
         game.place_ship(Ship::default()).unwrap();
-        assert_eq!(game.blue_ships.len(), 1);
-        game.toggle_active_player();
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
+        game.next_player();
         game.place_attack(Coordinates { x: 0, y: 0 }).unwrap();
         let result = game.place_attack(Coordinates { x: 0, y: 0 });
         assert!(result.is_err());
-        assert_eq!(game.blue_attacks.len(), 1);
+        assert_eq!(game.fleet(Faction::Blue).attacks.len(), 1);
     }
 
     #[test]
     fn test_auto_select_origin_empty_board() {
         let game = Game::default();
         let origin = game.auto_select_origin().unwrap();
-        assert!(origin.x >= 0);
         assert!(origin.x <= 7);
-        assert!(origin.y >= 0);
         assert!(origin.y <= 7);
     }
 
@@ -394,30 +1272,65 @@ mod tests {
     #[test]
     fn test_auto_create_5_ships() {
         let mut game = Game::default();
-        for length in vec![2, 2, 3, 4, 5] {
+        for length in [2, 2, 3, 4, 5].iter() {
             let ship = game
-                .auto_create_ship(length)
+                .auto_create_ship(*length)
                 .expect("Should have been able to create the ship");
             game.place_ship(ship)
                 .expect("Should have been able to place ship");
         }
-        assert_eq!(game.blue_ships.len(), 5);
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 5);
+    }
+
+    #[test]
+    fn test_bot_fleet_fits_a_crowded_map() {
+        for _ in 0..20 {
+            let mut game = Game {
+                board: Board::from_map(".....\n.....\n.....\n....#\n").unwrap(),
+                ..Game::default()
+            };
+            game.active_player = Faction::Red;
+            game.place_bot_fleet().unwrap();
+            assert_eq!(game.fleet(Faction::Red).ships.len(), 5);
+            assert_eq!(game.fleet(Faction::Red).mines.len(), 2);
+        }
+    }
+
+    #[test]
+    fn test_hard_bot_spreads_its_fleet() {
+        let mut game = Game {
+            difficulty: Difficulty::Hard,
+            ..Game::default()
+        };
+        game.active_player = Faction::Red;
+        game.place_bot_fleet().unwrap();
+        let ships = &game.fleet(Faction::Red).ships;
+        assert_eq!(ships.len(), 5);
+        for (index, ship) in ships.iter().enumerate() {
+            for other in ships[index + 1..].iter() {
+                for a in ship.get_segment_coordinates().iter() {
+                    for b in other.get_segment_coordinates().iter() {
+                        let apart =
+                            (a.x as i32 - b.x as i32).abs() + (a.y as i32 - b.y as i32).abs();
+                        assert!(apart > 1);
+                    }
+                }
+            }
+        }
     }
 
     #[test]
     fn test_auto_plan_attack_should_succeed() {
         let game = Game::default();
         let origin = game.auto_plan_attack().unwrap();
-        assert!(origin.x >= 0);
         assert!(origin.x <= 7);
-        assert!(origin.y >= 0);
         assert!(origin.y <= 7);
     }
 
     #[test]
     fn test_auto_plan_attack_should_fail_on_full_board() {
         let mut game = Game::default();
-        game.toggle_active_player();
+        game.next_player();
         for _ in 0..64 {
             let origin = game.auto_plan_attack().unwrap();
             game.place_attack(origin).unwrap();
@@ -433,4 +1346,781 @@ mod tests {
         let love = true;
         assert!(love);
     }
+
+    fn terrain_game() -> Game {
+        Game {
+            board: Board::from_map(
+                "..#.....\n~.......\n........\n........\n........\n........\n........\n........",
+            )
+            .unwrap(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_ships_should_not_be_placed_on_islands() {
+        let mut game = terrain_game();
+        let result = game.place_ship(Ship::new(Coordinates { x: 1, y: 0 }, Heading::East, 2));
+        assert!(result.is_err());
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
+    }
+
+    #[test]
+    fn test_ships_should_not_be_placed_on_reefs() {
+        let mut game = terrain_game();
+        let result = game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::South, 2));
+        assert!(result.is_err());
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 0);
+    }
+
+    #[test]
+    fn test_attack_on_island_is_wasted() {
+        let mut game = terrain_game();
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 2, y: 0 }).unwrap();
+        assert_eq!(attack.result, AttackResult::Miss);
+        assert_eq!(game.fleet(Faction::Red).score.misses, 1);
+    }
+
+    #[test]
+    fn test_attack_on_reef_is_allowed() {
+        let mut game = terrain_game();
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 0, y: 1 });
+        assert!(attack.is_ok());
+    }
+
+    #[test]
+    fn test_auto_plan_attack_should_skip_terrain() {
+        let mut game = terrain_game();
+        game.next_player();
+        for _ in 0..62 {
+            let origin = game.auto_plan_attack().unwrap();
+            assert!(game.board.terrain_at(&origin).is_navigable());
+            game.place_attack(origin).unwrap();
+        }
+        assert!(game.auto_plan_attack().is_err());
+    }
+
+    #[test]
+    fn test_place_mine() {
+        let mut game = Game::default();
+        let result = game.place_mine(Mine::new(Coordinates { x: 3, y: 3 }));
+        assert!(result.is_ok());
+        assert_eq!(game.fleet(Faction::Blue).mines.len(), 1);
+    }
+
+    #[test]
+    fn test_mines_should_not_share_a_cell_with_ships() {
+        let mut game = Game::default();
+        game.place_ship(Ship::default()).unwrap();
+        let result = game.place_mine(Mine::new(Coordinates { x: 1, y: 0 }));
+        assert!(result.is_err());
+        game.place_mine(Mine::new(Coordinates { x: 3, y: 3 }))
+            .unwrap();
+        let result = game.place_ship(Ship::new(Coordinates { x: 2, y: 3 }, Heading::East, 2));
+        assert!(result.is_err());
+        assert_eq!(game.fleet(Faction::Blue).ships.len(), 1);
+    }
+
+    #[test]
+    fn test_attack_on_mine_costs_a_turn_without_ships_to_reveal() {
+        let mut game = Game::default();
+        game.place_mine(Mine::new(Coordinates { x: 3, y: 3 }))
+            .unwrap();
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 3, y: 3 }).unwrap();
+        assert_eq!(attack.result, AttackResult::Mine(MineEffect::LoseTurn));
+        assert_eq!(game.fleet(Faction::Blue).mines.len(), 0);
+        assert_eq!(game.fleet(Faction::Red).score.misses, 1);
+        assert!(game.consume_lost_turn());
+        assert!(!game.consume_lost_turn());
+    }
+
+    #[test]
+    fn test_attack_on_mine_reveals_or_costs_a_turn() {
+        let mut game = Game::default();
+        game.place_mine(Mine::new(Coordinates { x: 3, y: 3 }))
+            .unwrap();
+        game.next_player();
+        game.place_ship(Ship::default()).unwrap();
+        let attack = game.place_attack(Coordinates { x: 3, y: 3 }).unwrap();
+        match attack.result {
+            AttackResult::Mine(MineEffect::Reveal(coords)) => {
+                assert_eq!(game.fleet(Faction::Red).revealed, vec![coords]);
+                assert_eq!(coords.y, 0);
+                assert!(coords.x <= 1);
+                assert_eq!(game.fleet(Faction::Red).turns_lost, 0);
+            }
+            AttackResult::Mine(MineEffect::LoseTurn) => {
+                assert_eq!(game.fleet(Faction::Red).turns_lost, 1);
+            }
+            _ => panic!("The attack should have hit the mine"),
+        }
+    }
+
+    #[test]
+    fn test_auto_plan_attack_should_target_revealed_segments() {
+        let mut game = Game::default();
+        game.fleet_mut(Faction::Blue)
+            .revealed
+            .push(Coordinates { x: 5, y: 6 });
+        game.next_player();
+        let origin = game.auto_plan_attack().unwrap();
+        assert_eq!(origin, Coordinates { x: 5, y: 6 });
+    }
+
+    #[test]
+    fn test_auto_create_mine_avoids_ships() {
+        let mut game = Game::default();
+        game.place_ship(Ship::default()).unwrap();
+        for _ in 0..10 {
+            let mine = game.auto_create_mine().unwrap();
+            game.place_mine(mine).unwrap();
+        }
+        assert_eq!(game.fleet(Faction::Blue).mines.len(), 10);
+    }
+
+    #[test]
+    fn test_radar_reports_ships_without_damage() {
+        let mut game = Game::default();
+        game.place_ship(Ship::default()).unwrap();
+        game.next_player();
+        match game
+            .launch(Weapon::Radar, Coordinates { x: 2, y: 1 })
+            .unwrap()
+        {
+            Strike::Ping(ping) => assert!(ping.found),
+            _ => panic!("Radar should produce a ping"),
+        }
+        match game
+            .launch(Weapon::Radar, Coordinates { x: 5, y: 5 })
+            .unwrap()
+        {
+            Strike::Ping(ping) => assert!(!ping.found),
+            _ => panic!("Radar should produce a ping"),
+        }
+        assert_eq!(game.fleet(Faction::Blue).attacks.len(), 0);
+        assert_eq!(game.fleet(Faction::Blue).pings.len(), 2);
+        assert_eq!(game.fleet(Faction::Red).arsenal.radar, 0);
+    }
+
+    #[test]
+    fn test_special_weapons_run_out() {
+        let mut game = Game::default();
+        game.launch(Weapon::Torpedo, Coordinates { x: 0, y: 3 })
+            .unwrap();
+        let result = game.launch(Weapon::Torpedo, Coordinates { x: 0, y: 4 });
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_engine_strikes_are_checked_before_launch() {
+        let mut game = Game {
+            board: Board::new(3, 1),
+            ..Default::default()
+        };
+        for x in 0..3 {
+            game.fleet_mut(Faction::Red)
+                .attacks
+                .push(Attack::with_result(
+                    Coordinates { x, y: 0 },
+                    AttackResult::Miss,
+                ));
+        }
+        let cell = Coordinates { x: 1, y: 0 };
+        assert_eq!(
+            game.check_engine_strike(vec![Command::Fire(Weapon::Airstrike, cell)]),
+            Err("There's nothing left to hit there")
+        );
+        assert_eq!(
+            game.check_engine_strike(vec![Command::Fire(Weapon::Shot, cell)]),
+            Err("You've already fired there")
+        );
+        game.fleet_mut(Faction::Blue).arsenal.radar = 0;
+        assert_eq!(
+            game.check_engine_strike(vec![Command::Fire(Weapon::Radar, cell)]),
+            Err("You're out of that weapon")
+        );
+        assert_eq!(
+            game.check_engine_strike(vec![Command::Fire(Weapon::Torpedo, cell)]),
+            Ok((Weapon::Torpedo, cell))
+        );
+    }
+
+    #[test]
+    fn test_airstrike_hits_a_row_segment() {
+        let mut game = Game::default();
+        game.place_ship(Ship::default()).unwrap();
+        game.next_player();
+        match game
+            .launch(Weapon::Airstrike, Coordinates { x: 0, y: 0 })
+            .unwrap()
+        {
+            Strike::Attacks(attacks) => {
+                // Clipped by the western edge of the board.
+                assert_eq!(attacks.len(), 2);
+                assert!(attacks.iter().all(|a| a.result == AttackResult::Hit));
+            }
+            _ => panic!("An airstrike should produce attacks"),
+        }
+        assert_eq!(game.fleet(Faction::Red).score.hits, 2);
+    }
+
+    #[test]
+    fn test_torpedo_stops_at_the_first_ship() {
+        let mut game = Game::default();
+        game.place_ship(Ship::new(Coordinates { x: 4, y: 2 }, Heading::East, 3))
+            .unwrap();
+        game.next_player();
+        match game
+            .launch(Weapon::Torpedo, Coordinates { x: 1, y: 2 })
+            .unwrap()
+        {
+            Strike::Attacks(attacks) => {
+                assert_eq!(attacks.len(), 1);
+                assert_eq!(attacks[0].coordinates, Coordinates { x: 4, y: 2 });
+                assert_eq!(attacks[0].result, AttackResult::Hit);
+            }
+            _ => panic!("A torpedo should produce attacks"),
+        }
+    }
+
+    #[test]
+    fn test_torpedo_starts_from_the_aimed_cell() {
+        let mut game = Game::default();
+        game.place_ship(Ship::new(Coordinates { x: 4, y: 2 }, Heading::East, 3))
+            .unwrap();
+        game.next_player();
+        match game
+            .launch(Weapon::Torpedo, Coordinates { x: 7, y: 2 })
+            .unwrap()
+        {
+            // Launched east of the ship, so it never passes it.
+            Strike::Attacks(attacks) => assert!(attacks.is_empty()),
+            _ => panic!("A torpedo should produce attacks"),
+        }
+    }
+
+    #[test]
+    fn test_torpedo_passes_reefs_and_stops_at_islands() {
+        let mut game = Game {
+            board: Board::from_map("~..#....\n........").unwrap(),
+            ..Default::default()
+        };
+        game.next_player();
+        match game
+            .launch(Weapon::Torpedo, Coordinates { x: 0, y: 0 })
+            .unwrap()
+        {
+            Strike::Attacks(attacks) => {
+                assert_eq!(attacks.len(), 1);
+                assert_eq!(attacks[0].coordinates, Coordinates { x: 3, y: 0 });
+                assert_eq!(attacks[0].result, AttackResult::Miss);
+            }
+            _ => panic!("A torpedo should produce attacks"),
+        }
+    }
+
+    #[test]
+    fn test_torpedo_can_run_off_the_board() {
+        let mut game = Game::default();
+        game.next_player();
+        match game
+            .launch(Weapon::Torpedo, Coordinates { x: 0, y: 5 })
+            .unwrap()
+        {
+            Strike::Attacks(attacks) => assert!(attacks.is_empty()),
+            _ => panic!("A torpedo should produce attacks"),
+        }
+        assert_eq!(game.fleet(Faction::Red).arsenal.torpedo, 0);
+    }
+
+    #[test]
+    fn test_auto_plan_attack_follows_radar_contacts() {
+        let mut game = Game::default();
+        game.fleet_mut(Faction::Blue)
+            .pings
+            .push(Ping::new(Coordinates { x: 6, y: 6 }, true));
+        game.next_player();
+        let origin = game.auto_plan_attack().unwrap();
+        assert!(origin.x >= 5 && origin.y >= 5);
+    }
+
+    #[test]
+    fn test_auto_plan_attack_skips_cleared_areas() {
+        let mut game = Game::default();
+        game.fleet_mut(Faction::Blue)
+            .pings
+            .push(Ping::new(Coordinates { x: 1, y: 1 }, false));
+        game.next_player();
+        for _ in 0..55 {
+            let origin = game.auto_plan_attack().unwrap();
+            assert!(origin.x > 2 || origin.y > 2);
+            game.place_attack(origin).unwrap();
+        }
+        assert!(game.auto_plan_attack().is_err());
+    }
+
+    #[test]
+    fn test_move_ship_requires_moving_fleet() {
+        let mut game = Game::default();
+        game.place_ship(Ship::default()).unwrap();
+        assert!(game.move_ship(0, Direction::Down).is_err());
+        assert_eq!(
+            game.fleet(Faction::Blue).ships[0].origin,
+            Coordinates { x: 0, y: 0 }
+        );
+    }
+
+    #[test]
+    fn test_move_ship_makes_misses_stale() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::default()).unwrap();
+        game.next_player();
+        game.place_attack(Coordinates { x: 0, y: 1 }).unwrap();
+        game.next_player();
+
+        game.move_ship(0, Direction::Down).unwrap();
+        assert_eq!(
+            game.fleet(Faction::Blue).ships[0].origin,
+            Coordinates { x: 0, y: 1 }
+        );
+        assert!(game.fleet(Faction::Blue).attacks[0].stale);
+
+        // The stale miss can be fired at again, and now it's a hit.
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 0, y: 1 }).unwrap();
+        assert_eq!(attack.result, AttackResult::Hit);
+        assert_eq!(game.fleet(Faction::Blue).attacks.len(), 1);
+    }
+
+    #[test]
+    fn test_ship_moved_onto_a_spent_mine_can_be_sunk() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::default()).unwrap();
+        game.place_mine(Mine::new(Coordinates { x: 0, y: 1 }))
+            .unwrap();
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 0, y: 1 }).unwrap();
+        assert!(matches!(attack.result, AttackResult::Mine(_)));
+        game.next_player();
+
+        // The mine went off, so the ship can sail into its cell.
+        game.move_ship(0, Direction::Down).unwrap();
+        assert!(game.fleet(Faction::Blue).attacks[0].stale);
+
+        game.next_player();
+        let attack = game.place_attack(Coordinates { x: 0, y: 1 }).unwrap();
+        assert_eq!(attack.result, AttackResult::Hit);
+        game.place_attack(Coordinates { x: 1, y: 1 }).unwrap();
+        assert!(game.fleet(Faction::Blue).is_eliminated());
+    }
+
+    #[test]
+    fn test_damaged_ships_cannot_move() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::default()).unwrap();
+        game.next_player();
+        game.place_attack(Coordinates { x: 1, y: 0 }).unwrap();
+        game.next_player();
+        assert!(game.movable_ships().is_empty());
+        assert!(game.move_ship(0, Direction::Down).is_err());
+    }
+
+    #[test]
+    fn test_ships_cannot_move_off_the_board_or_into_others() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::default()).unwrap();
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 1 }, Heading::East, 2))
+            .unwrap();
+        assert!(game.move_ship(0, Direction::Up).is_err());
+        assert!(game.move_ship(0, Direction::Left).is_err());
+        assert!(game.move_ship(0, Direction::Down).is_err());
+        assert!(game.move_ship(0, Direction::Right).is_ok());
+    }
+
+    #[test]
+    fn test_auto_plan_attack_revisits_stale_misses_last() {
+        let mut game = Game {
+            board: Board::new(2, 1),
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::new(Coordinates { x: 1, y: 0 }, Heading::East, 1))
+            .unwrap();
+        game.next_player();
+        game.place_attack(Coordinates { x: 0, y: 0 }).unwrap();
+        game.next_player();
+        game.move_ship(0, Direction::Left).unwrap();
+        game.next_player();
+
+        let origin = game.auto_plan_attack().unwrap();
+        assert_eq!(origin, Coordinates { x: 1, y: 0 });
+        game.place_attack(origin).unwrap();
+        let origin = game.auto_plan_attack().unwrap();
+        assert_eq!(origin, Coordinates { x: 0, y: 0 });
+    }
+
+    #[test]
+    fn test_auto_plan_move_escapes_radar_contacts() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.next_player();
+        game.place_ship(Ship::new(Coordinates { x: 3, y: 3 }, Heading::East, 2))
+            .unwrap();
+        assert!(game.auto_plan_move().is_none());
+        game.fleet_mut(Faction::Red)
+            .pings
+            .push(Ping::new(Coordinates { x: 3, y: 3 }, true));
+        let (index, direction) = game.auto_plan_move().unwrap();
+        assert_eq!(index, 0);
+        game.move_ship(index, direction).unwrap();
+        assert!(game.fleet(Faction::Red).pings.is_empty());
+    }
+
+    fn sink_fleet(game: &mut Game, faction: Faction) {
+        let active = game.active_player;
+        game.active_player = faction;
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 1))
+            .unwrap();
+        game.active_player = active;
+        game.fleet_mut(faction).attacks.push(Attack::with_result(
+            Coordinates { x: 0, y: 0 },
+            AttackResult::Hit,
+        ));
+    }
+
+    #[test]
+    fn test_new_seats_factions_in_turn_order() {
+        let game = Game::new(4);
+        let factions: Vec<Faction> = game.fleets.iter().map(|fleet| fleet.faction).collect();
+        assert_eq!(factions, Faction::all());
+        assert_eq!(game.active_player, Faction::Blue);
+        assert_eq!(game.target, Faction::Red);
+    }
+
+    #[test]
+    fn test_next_player_cycles_through_every_faction() {
+        let mut game = Game::new(3);
+        game.next_player();
+        assert_eq!(game.active_player, Faction::Red);
+        assert_eq!(game.target, Faction::Green);
+        game.next_player();
+        assert_eq!(game.active_player, Faction::Green);
+        assert_eq!(game.target, Faction::Blue);
+        game.next_player();
+        assert_eq!(game.active_player, Faction::Blue);
+        assert_eq!(game.target, Faction::Red);
+    }
+
+    #[test]
+    fn test_next_player_skips_eliminated_factions() {
+        let mut game = Game::new(3);
+        sink_fleet(&mut game, Faction::Red);
+        game.next_player();
+        assert_eq!(game.active_player, Faction::Green);
+        assert_eq!(game.target, Faction::Blue);
+        game.next_player();
+        assert_eq!(game.target, Faction::Green);
+    }
+
+    #[test]
+    fn test_select_target() {
+        let mut game = Game::new(3);
+        assert!(game.select_target(Faction::Green).is_ok());
+        assert_eq!(game.target, Faction::Green);
+        assert!(game.select_target(Faction::Blue).is_err());
+        assert!(game.select_target(Faction::Yellow).is_err());
+        sink_fleet(&mut game, Faction::Red);
+        assert!(game.select_target(Faction::Red).is_err());
+        assert_eq!(game.target, Faction::Green);
+    }
+
+    #[test]
+    fn test_attacks_land_on_the_target_fleet() {
+        let mut game = Game::new(3);
+        game.select_target(Faction::Green).unwrap();
+        game.place_attack(Coordinates { x: 0, y: 0 }).unwrap();
+        assert_eq!(game.fleet(Faction::Green).attacks.len(), 1);
+        assert!(game.fleet(Faction::Red).attacks.is_empty());
+        // The same cell is still open on another board.
+        game.select_target(Faction::Red).unwrap();
+        assert!(game.place_attack(Coordinates { x: 0, y: 0 }).is_ok());
+        assert_eq!(game.fleet(Faction::Blue).score.misses, 2);
+    }
+
+    #[test]
+    fn test_winner_is_last_fleet_afloat() {
+        let mut game = Game::new(3);
+        assert_eq!(game.winner(), None);
+        sink_fleet(&mut game, Faction::Red);
+        assert_eq!(game.winner(), None);
+        assert_eq!(game.opponents(), vec![Faction::Green]);
+        sink_fleet(&mut game, Faction::Green);
+        assert_eq!(game.winner(), Some(Faction::Blue));
+    }
+
+    #[test]
+    fn test_auto_plan_target_prefers_leads() {
+        let mut game = Game::new(3);
+        game.next_player();
+        assert_eq!(game.auto_plan_target(), Faction::Green);
+        game.fleet_mut(Faction::Blue)
+            .revealed
+            .push(Coordinates { x: 2, y: 2 });
+        assert_eq!(game.auto_plan_target(), Faction::Blue);
+    }
+
+    #[test]
+    fn test_random_attack_picks_an_open_cell() {
+        let mut game = Game {
+            board: Board::new(2, 1),
+            ..Default::default()
+        };
+        game.place_attack(Coordinates { x: 0, y: 0 }).unwrap();
+        assert_eq!(game.random_attack(), Ok(Coordinates { x: 1, y: 0 }));
+        game.place_attack(Coordinates { x: 1, y: 0 }).unwrap();
+        assert!(game.random_attack().is_err());
+    }
+
+    #[test]
+    fn test_history_records_each_turn() {
+        let mut game = Game {
+            moving_fleet: true,
+            ..Default::default()
+        };
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 2))
+            .unwrap();
+        game.launch(Weapon::Shot, Coordinates { x: 4, y: 4 })
+            .unwrap();
+        game.move_ship(0, Direction::Down).unwrap();
+        game.fleet_mut(Faction::Blue).turns_lost = 1;
+        assert!(game.consume_lost_turn());
+
+        assert_eq!(game.history.len(), 3);
+        assert!(game
+            .history
+            .iter()
+            .all(|turn| turn.faction == Faction::Blue));
+        assert!(matches!(
+            game.history[0].play,
+            Play::Fire {
+                weapon: Weapon::Shot,
+                target: Faction::Red,
+                ..
+            }
+        ));
+        assert!(matches!(game.history[1].play, Play::Move));
+        assert!(matches!(game.history[2].play, Play::LostTurn));
+    }
+
+    #[test]
+    fn test_stats_from_history() {
+        let mut game = Game::default();
+        game.next_player();
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 3))
+            .unwrap();
+        game.next_player();
+        for x in [0, 1, 5, 2].iter() {
+            game.launch(Weapon::Shot, Coordinates { x: *x, y: 0 })
+                .unwrap();
+        }
+        game.launch(Weapon::Radar, Coordinates { x: 5, y: 5 })
+            .unwrap();
+
+        let stats = Stats::new(&game.history, Faction::Blue);
+        assert_eq!(stats.turns, 5);
+        assert_eq!(stats.shots, 4);
+        assert_eq!(stats.hits, 3);
+        assert_eq!(stats.accuracy(), 75);
+        assert_eq!(stats.longest_streak, 2);
+        assert_eq!(Stats::new(&game.history, Faction::Red), Stats::default());
+    }
+
+    #[test]
+    fn test_easy_ai_only_fires_plain_shots() {
+        let mut game = Game {
+            difficulty: Difficulty::Easy,
+            ..Default::default()
+        };
+        game.next_player();
+        game.fleet_mut(Faction::Blue)
+            .revealed
+            .push(Coordinates { x: 2, y: 2 });
+        assert!(game.auto_find_leads().is_empty());
+        for _ in 0..50 {
+            let (weapon, _) = game.auto_plan_strike().unwrap();
+            assert_eq!(weapon, Weapon::Shot);
+        }
+    }
+
+    #[test]
+    fn test_hard_ai_closes_in_around_hits() {
+        let mut game = Game {
+            difficulty: Difficulty::Hard,
+            ..Default::default()
+        };
+        game.place_ship(Ship::new(Coordinates { x: 3, y: 3 }, Heading::East, 2))
+            .unwrap();
+        game.next_player();
+        game.place_attack(Coordinates { x: 3, y: 3 }).unwrap();
+        game.place_attack(Coordinates { x: 3, y: 2 }).unwrap();
+
+        let neighbours = [
+            Coordinates { x: 4, y: 3 },
+            Coordinates { x: 2, y: 3 },
+            Coordinates { x: 3, y: 4 },
+        ];
+        let origin = game.auto_plan_attack().unwrap();
+        assert!(neighbours.contains(&origin));
+        let (weapon, _) = game.auto_plan_strike().unwrap();
+        assert_eq!(weapon, Weapon::Shot);
+    }
+
+    #[test]
+    fn test_hard_ai_searches_a_checkerboard() {
+        let mut game = Game {
+            difficulty: Difficulty::Hard,
+            ..Default::default()
+        };
+        game.next_player();
+        for _ in 0..20 {
+            let origin = game.auto_plan_attack().unwrap();
+            assert_eq!((origin.x + origin.y) % 2, 0);
+        }
+    }
+
+    #[test]
+    fn test_record_summarises_the_players_game() {
+        let mut game = Game {
+            difficulty: Difficulty::Hard,
+            ..Default::default()
+        };
+        game.next_player();
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 2))
+            .unwrap();
+        game.next_player();
+        game.launch(Weapon::Shot, Coordinates { x: 0, y: 0 })
+            .unwrap();
+        game.launch(Weapon::Shot, Coordinates { x: 1, y: 0 })
+            .unwrap();
+
+        let record = game.record();
+        assert!(record.won);
+        assert_eq!(record.difficulty, Difficulty::Hard);
+        assert_eq!((record.shots, record.hits, record.turns), (2, 2, 2));
+    }
+
+    #[test]
+    fn test_fleet_difficulty_overrides_the_game() {
+        let mut game = Game {
+            difficulty: Difficulty::Easy,
+            ..Default::default()
+        };
+        game.fleet_mut(Faction::Red).difficulty = Some(Difficulty::Hard);
+        assert_eq!(game.ai_difficulty(), Difficulty::Easy);
+        game.next_player();
+        assert_eq!(game.ai_difficulty(), Difficulty::Hard);
+    }
+
+    #[test]
+    fn test_rating_names() {
+        let mut game = Game {
+            difficulty: Difficulty::Hard,
+            profile: Profile::new("Ada"),
+            ..Default::default()
+        };
+        assert_eq!(game.rating_name(Faction::Blue), "Ada");
+        assert_eq!(game.rating_name(Faction::Red), "Bot (Hard)");
+        game.fleet_mut(Faction::Blue).difficulty = Some(Difficulty::Easy);
+        assert_eq!(game.rating_name(Faction::Blue), "Bot (Easy)");
+    }
+
+    #[test]
+    fn test_faction_name_uses_the_profile_for_the_player() {
+        let game = Game {
+            profile: Profile::new("Ada"),
+            ..Default::default()
+        };
+        assert_eq!(game.faction_name(Faction::Blue), "Ada");
+        assert_eq!(game.faction_name(Faction::Red), "Red Team");
+    }
+
+    #[test]
+    fn test_auto_play_turn_until_someone_wins() {
+        let mut game = Game::new(3);
+        for faction in Faction::all().into_iter().take(3) {
+            game.active_player = faction;
+            game.auto_place_fleet(&Placeable::fleet()).unwrap();
+        }
+        game.active_player = Faction::Blue;
+        for _ in 0..1000 {
+            game.auto_play_turn().unwrap();
+            if game.winner().is_some() {
+                break;
+            }
+            game.next_player();
+        }
+        assert!(game.winner().is_some());
+    }
+
+    #[test]
+    fn test_rate_settles_the_game() {
+        let mut game = Game {
+            profile: Profile::new("Ada"),
+            ..Game::new(3)
+        };
+        let mut ratings = Ratings::default();
+        // The player and a bot are still fighting, so there's nothing to
+        // settle yet.
+        sink_fleet(&mut game, Faction::Red);
+        assert!(!game.rate(&mut ratings));
+        sink_fleet(&mut game, Faction::Green);
+        assert!(game.rate(&mut ratings));
+        assert!(ratings.rating("Ada") > ratings.rating("Bot (Normal)"));
+    }
+
+    #[test]
+    fn test_rate_counts_each_commander_once() {
+        let mut game = Game::new(4);
+        for (faction, difficulty) in Faction::all().into_iter().zip(
+            [
+                Difficulty::Hard,
+                Difficulty::Easy,
+                Difficulty::Hard,
+                Difficulty::Easy,
+            ]
+            .iter(),
+        ) {
+            game.fleet_mut(faction).difficulty = Some(*difficulty);
+        }
+        sink_fleet(&mut game, Faction::Red);
+        sink_fleet(&mut game, Faction::Yellow);
+        let mut ratings = Ratings::default();
+        assert!(game.rate(&mut ratings));
+        let games = |name: &str| {
+            ratings
+                .leaderboard()
+                .into_iter()
+                .find(|entry| entry.name == name)
+                .map(|entry| entry.games)
+        };
+        assert_eq!(games("Bot (Hard)"), Some(1));
+        assert_eq!(games("Bot (Easy)"), Some(1));
+    }
 }
@@ -3,8 +3,9 @@ use rand::{
     Rng,
 };
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
 pub enum Heading {
+    #[default]
     East,
     South,
 }
@@ -18,12 +19,6 @@ impl Heading {
     }
 }
 
-impl Default for Heading {
-    fn default() -> Self {
-        Heading::East
-    }
-}
-
 impl Distribution<Heading> for Standard {
     fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Heading {
         match rng.gen_range(0, 1) {
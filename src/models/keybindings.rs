@@ -0,0 +1,37 @@
+// Which keys move the cursor around a board.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Keybindings {
+    #[default]
+    Wasd,
+    Arrows,
+    // h, j, k and l as in vi.
+    Vim,
+}
+
+impl Keybindings {
+    pub fn all() -> Vec<Keybindings> {
+        vec![Keybindings::Wasd, Keybindings::Arrows, Keybindings::Vim]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Keybindings::Wasd => "WASD",
+            Keybindings::Arrows => "Arrows",
+            Keybindings::Vim => "Vim",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Keybindings> {
+        Keybindings::all()
+            .into_iter()
+            .find(|keybindings| keybindings.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn next(self) -> Keybindings {
+        match self {
+            Keybindings::Wasd => Keybindings::Arrows,
+            Keybindings::Arrows => Keybindings::Vim,
+            Keybindings::Vim => Keybindings::Wasd,
+        }
+    }
+}
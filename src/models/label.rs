@@ -1,3 +1,4 @@
+#[derive(Clone)]
 pub struct Label {
     pub content: String,
 }
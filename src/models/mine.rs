@@ -0,0 +1,21 @@
+use crate::models::Coordinates;
+
+#[derive(Debug, Copy, Clone)]
+pub struct Mine {
+    pub coordinates: Coordinates,
+}
+
+impl Mine {
+    pub fn new(coordinates: Coordinates) -> Mine {
+        Mine { coordinates }
+    }
+}
+
+// What happens to a commander who fires into one of the enemy's mines.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum MineEffect {
+    // One of the shooter's own ship segments is given away.
+    Reveal(Coordinates),
+    // The shooter sits out their next turn.
+    LoseTurn,
+}
@@ -1,18 +1,50 @@
+mod achievement;
 mod alert;
+mod arsenal;
 mod attack;
 mod attack_result;
 mod board;
+mod clock;
 mod coordinates;
 mod cursor;
+mod difficulty;
+mod direction;
+mod engine;
 mod faction;
+mod fleet;
 mod game;
 mod heading;
+mod keybindings;
 mod label;
+mod mine;
+mod ping;
+mod placeable;
+mod placement;
+mod profile;
+mod protocol;
+mod ratings;
+mod records;
+mod rng;
+mod sampler;
 mod scores;
 mod ship;
+mod shot_chart;
+mod stats;
+mod strike;
+mod terrain;
+mod theme;
+mod tournament;
+mod turn;
+mod weapon;
 
 pub use self::{
-    alert::Alert, alert::Level, attack::Attack, attack_result::AttackResult, board::Board,
-    coordinates::Coordinates, cursor::Cursor, faction::Faction, game::Game, heading::Heading,
-    label::Label, scores::Scores, ship::Ship,
+    achievement::Achievement, alert::Alert, alert::Level, arsenal::Arsenal, attack::Attack,
+    attack_result::AttackResult, board::Board, clock::Clock, coordinates::Coordinates,
+    cursor::Cursor, difficulty::Difficulty, direction::Direction, engine::Engine, faction::Faction,
+    fleet::Fleet, game::Game, heading::Heading, keybindings::Keybindings, label::Label, mine::Mine,
+    mine::MineEffect, ping::Ping, placeable::Placeable, placement::Placement, profile::Profile,
+    profile::Profiles, protocol::report, protocol::Command, ratings::Ratings, records::GameRecord,
+    records::Records, rng::rng, rng::seed, sampler::heat_map, scores::Scores, ship::Ship,
+    shot_chart::ShotChart, stats::Stats, strike::Strike, terrain::Terrain, theme::Theme,
+    tournament::Entrant, tournament::Tournament, turn::Play, turn::Turn, weapon::Weapon,
 };
@@ -0,0 +1,30 @@
+use crate::models::Coordinates;
+
+// The result of a radar sweep over the 3x3 area centred on `origin`.
+#[derive(Debug, Copy, Clone)]
+pub struct Ping {
+    pub origin: Coordinates,
+    pub found: bool,
+}
+
+impl Ping {
+    pub fn new(origin: Coordinates, found: bool) -> Ping {
+        Ping { origin, found }
+    }
+
+    pub fn covers(&self, coordinates: &Coordinates) -> bool {
+        (self.origin.x as i32 - coordinates.x as i32).abs() <= 1
+            && (self.origin.y as i32 - coordinates.y as i32).abs() <= 1
+    }
+
+    // The cells swept, clipped to the top and left edges of the board.
+    pub fn area(&self) -> Vec<Coordinates> {
+        let mut cells = vec![];
+        for y in self.origin.y.saturating_sub(1)..=self.origin.y + 1 {
+            for x in self.origin.x.saturating_sub(1)..=self.origin.x + 1 {
+                cells.push(Coordinates { x, y });
+            }
+        }
+        cells
+    }
+}
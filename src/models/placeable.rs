@@ -0,0 +1,21 @@
+// Anything a commander puts on their own board during setup.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Placeable {
+    Ship(u16),
+    Mine,
+}
+
+impl Placeable {
+    // Everything each commander places, in order of placement.
+    pub fn fleet() -> Vec<Placeable> {
+        vec![
+            Placeable::Ship(5),
+            Placeable::Ship(4),
+            Placeable::Ship(3),
+            Placeable::Ship(2),
+            Placeable::Ship(2),
+            Placeable::Mine,
+            Placeable::Mine,
+        ]
+    }
+}
@@ -0,0 +1,203 @@
+use rand::seq::SliceRandom;
+
+use crate::models::{rng, Board, Coordinates, Ship};
+
+// How the computer lays out its fleet.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Placement {
+    // Wherever a random origin and heading happen to fit.
+    #[default]
+    Random,
+    // Never lets two ships touch, so finding one gives nothing away about
+    // the next.
+    Spread,
+    // Hugs the edges of the board, where searches often reach last.
+    Edges,
+    // Spreads out and steers clear of where the opponent usually fires.
+    Evasive,
+}
+
+impl Placement {
+    pub fn all() -> Vec<Placement> {
+        vec![
+            Placement::Random,
+            Placement::Spread,
+            Placement::Edges,
+            Placement::Evasive,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Placement::Random => "Random",
+            Placement::Spread => "Spread",
+            Placement::Edges => "Edges",
+            Placement::Evasive => "Evasive",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Placement> {
+        Placement::all()
+            .into_iter()
+            .find(|placement| placement.name().eq_ignore_ascii_case(name))
+    }
+
+    // Picks one of `candidates`, every legal spot for the next ship, given
+    // the ships already placed and how often the opponent has fired at
+    // each cell, by row then column.
+    pub fn choose(
+        self,
+        board: &Board,
+        placed: &[Ship],
+        candidates: &[Ship],
+        shots: &[u32],
+    ) -> Option<Ship> {
+        let touches = |ship: &Ship| {
+            let segments = ship.get_segment_coordinates();
+            placed
+                .iter()
+                .flat_map(|other| other.get_segment_coordinates())
+                .any(|other| {
+                    segments.iter().any(|cell| {
+                        (cell.x as i32 - other.x as i32).abs()
+                            + (cell.y as i32 - other.y as i32).abs()
+                            == 1
+                    })
+                })
+        };
+        // Squeezed boards may leave no room to keep ships apart.
+        let apart: Vec<Ship> = candidates
+            .iter()
+            .copied()
+            .filter(|ship| !touches(ship))
+            .collect();
+        let spread = if apart.is_empty() {
+            candidates
+        } else {
+            &apart[..]
+        };
+
+        let on_edge = |cell: &Coordinates| {
+            cell.x == 0 || cell.y == 0 || cell.x + 1 == board.width || cell.y + 1 == board.height
+        };
+        let total: u32 = shots.iter().sum();
+        let fired_at = |ship: &Ship| -> f64 {
+            ship.get_segment_coordinates()
+                .iter()
+                .map(|cell| {
+                    shots
+                        .get(usize::from(cell.y) * usize::from(board.width) + usize::from(cell.x))
+                        .copied()
+                        .unwrap_or(0)
+                })
+                .sum::<u32>() as f64
+        };
+
+        let mut rng = rng();
+        match self {
+            Placement::Random => candidates.choose(&mut rng).copied(),
+            Placement::Spread => spread.choose(&mut rng).copied(),
+            Placement::Edges => candidates
+                .choose_weighted(&mut rng, |ship| {
+                    1 + 4 * ship
+                        .get_segment_coordinates()
+                        .iter()
+                        .filter(|cell| on_edge(cell))
+                        .count()
+                })
+                .ok()
+                .copied(),
+            Placement::Evasive if total == 0 => spread.choose(&mut rng).copied(),
+            Placement::Evasive => {
+                // How many shots a cell gets on average, so a ship sitting
+                // on twice the usual fire is a quarter as likely.
+                let usual = total as f64 / shots.len() as f64;
+                spread
+                    .choose_weighted(&mut rng, |ship| {
+                        let heat = fired_at(ship) / (usual * f64::from(ship.length));
+                        1.0 / (1.0 + heat).powi(2)
+                    })
+                    .ok()
+                    .copied()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{seed, Heading};
+
+    fn every_ship(board: &Board, length: u16) -> Vec<Ship> {
+        let mut ships = vec![];
+        for y in 0..board.height {
+            for x in 0..board.width {
+                for heading in [Heading::East, Heading::South].iter() {
+                    let ship = Ship::new(Coordinates { x, y }, *heading, length);
+                    if ship
+                        .get_segment_coordinates()
+                        .iter()
+                        .all(|cell| board.contains(cell))
+                    {
+                        ships.push(ship);
+                    }
+                }
+            }
+        }
+        ships
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(Placement::parse("evasive"), Some(Placement::Evasive));
+        assert_eq!(Placement::parse("clustered"), None);
+    }
+
+    #[test]
+    fn test_spread_keeps_ships_apart() {
+        let board = Board::new(8, 8);
+        let placed = vec![Ship::new(Coordinates { x: 3, y: 3 }, Heading::East, 3)];
+        let candidates: Vec<Ship> = every_ship(&board, 2)
+            .into_iter()
+            .filter(|ship| {
+                ship.get_segment_coordinates()
+                    .iter()
+                    .all(|cell| !placed[0].get_segment_coordinates().contains(cell))
+            })
+            .collect();
+        for _ in 0..50 {
+            let ship = Placement::Spread
+                .choose(&board, &placed, &candidates, &[])
+                .unwrap();
+            for cell in ship.get_segment_coordinates() {
+                assert!(
+                    !(2..=6).contains(&cell.x) || !(2..=4).contains(&cell.y) || {
+                        // Diagonal neighbours don't count as touching.
+                        (cell.x == 2 || cell.x == 6) && (cell.y == 2 || cell.y == 4)
+                    }
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_evasive_avoids_heavy_fire() {
+        seed(Some(7));
+        let board = Board::new(6, 1);
+        let candidates = every_ship(&board, 2);
+        // The opponent always fires at the first two cells.
+        let shots = [100, 100, 0, 0, 0, 0];
+        let mut under_fire = 0;
+        for _ in 0..100 {
+            let ship = Placement::Evasive
+                .choose(&board, &[], &candidates, &shots)
+                .unwrap();
+            if ship.origin.x < 2 {
+                under_fire += 1;
+            }
+        }
+        seed(None);
+        assert!(under_fire < 20);
+    }
+}
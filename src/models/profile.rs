@@ -0,0 +1,282 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::{Achievement, Difficulty, Keybindings, Records, Theme};
+
+// Board sizes a profile can pick from, smallest first.
+const BOARD_SIZES: [(u16, u16); 3] = [(8, 8), (10, 10), (12, 12)];
+
+// A named commander and how they like to play.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Profile {
+    pub name: String,
+    pub keybindings: Keybindings,
+    pub theme: Theme,
+    pub difficulty: Difficulty,
+    pub board_width: u16,
+    pub board_height: u16,
+    pub achievements: Vec<Achievement>,
+}
+
+impl Default for Profile {
+    fn default() -> Profile {
+        Profile::new("Commander")
+    }
+}
+
+impl Profile {
+    pub fn new(name: &str) -> Profile {
+        Profile {
+            name: name.to_string(),
+            keybindings: Keybindings::default(),
+            theme: Theme::default(),
+            difficulty: Difficulty::default(),
+            board_width: BOARD_SIZES[0].0,
+            board_height: BOARD_SIZES[0].1,
+            achievements: vec![],
+        }
+    }
+
+    // Names end up in tab separated files, so they can't hold a tab.
+    pub fn is_valid_name(name: &str) -> bool {
+        !name.trim().is_empty() && !name.contains('\t')
+    }
+
+    // Steps through the board sizes, wrapping back to the smallest.
+    pub fn next_board_size(&mut self) {
+        let current = (self.board_width, self.board_height);
+        let next = match BOARD_SIZES.iter().position(|size| *size == current) {
+            Some(index) => BOARD_SIZES[(index + 1) % BOARD_SIZES.len()],
+            None => BOARD_SIZES[0],
+        };
+        self.board_width = next.0;
+        self.board_height = next.1;
+    }
+
+    // Records `achievements`, returning the ones that are new.
+    pub fn unlock(&mut self, achievements: &[Achievement]) -> Vec<Achievement> {
+        let new: Vec<Achievement> = achievements
+            .iter()
+            .copied()
+            .filter(|achievement| !self.achievements.contains(achievement))
+            .collect();
+        self.achievements.extend(new.iter());
+        new
+    }
+
+    // Tab separated name, keybindings, theme, difficulty, board size and
+    // a comma separated list of achievements.
+    pub fn to_line(&self) -> String {
+        let achievements: Vec<&str> = self
+            .achievements
+            .iter()
+            .map(|achievement| achievement.name())
+            .collect();
+        format!(
+            "{}\t{}\t{}\t{}\t{}x{}\t{}",
+            self.name,
+            self.keybindings.name(),
+            self.theme.name(),
+            self.difficulty.name(),
+            self.board_width,
+            self.board_height,
+            achievements.join(",")
+        )
+    }
+
+    pub fn from_line(line: &str) -> Result<Profile, &'static str> {
+        let fields: Vec<&str> = line.trim_end().split('\t').collect();
+        // Profiles without any achievements leave the last field off.
+        let (name, keybindings, theme, difficulty, size, achievements) = match fields.as_slice() {
+            [name, keybindings, theme, difficulty, size] => {
+                (*name, *keybindings, *theme, *difficulty, *size, "")
+            }
+            [name, keybindings, theme, difficulty, size, achievements] => (
+                *name,
+                *keybindings,
+                *theme,
+                *difficulty,
+                *size,
+                *achievements,
+            ),
+            _ => return Err("A profile needs five or six fields"),
+        };
+        if !Profile::is_valid_name(name) {
+            return Err("Unknown profile name");
+        }
+        let keybindings = Keybindings::parse(keybindings).ok_or("Unknown keybindings")?;
+        let theme = Theme::parse(theme).ok_or("Unknown theme")?;
+        let difficulty = Difficulty::parse(difficulty).ok_or("Unknown difficulty")?;
+        let (board_width, board_height) = match size.split_once('x') {
+            Some((width, height)) => match (width.parse(), height.parse()) {
+                (Ok(width), Ok(height)) => (width, height),
+                _ => return Err("Unknown board size"),
+            },
+            None => return Err("Unknown board size"),
+        };
+        let achievements = achievements
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(|name| Achievement::parse(name).ok_or("Unknown achievement"))
+            .collect::<Result<Vec<Achievement>, &'static str>>()?;
+        Ok(Profile {
+            name: name.to_string(),
+            keybindings,
+            theme,
+            difficulty,
+            board_width,
+            board_height,
+            achievements,
+        })
+    }
+}
+
+// Every profile on this machine, most recently played first.
+#[derive(Debug, Default)]
+pub struct Profiles {
+    pub entries: Vec<Profile>,
+}
+
+impl Profiles {
+    fn path() -> Option<PathBuf> {
+        Records::data_dir().map(|dir| dir.join("profiles"))
+    }
+
+    pub fn from_lines(lines: &str) -> Profiles {
+        Profiles {
+            entries: lines
+                .lines()
+                .filter_map(|line| Profile::from_line(line).ok())
+                .collect(),
+        }
+    }
+
+    pub fn to_lines(&self) -> String {
+        self.entries
+            .iter()
+            .map(|profile| format!("{}\n", profile.to_line()))
+            .collect()
+    }
+
+    pub fn load() -> Profiles {
+        match Profiles::path().map(fs::read_to_string) {
+            Some(Ok(lines)) => Profiles::from_lines(&lines),
+            _ => Profiles::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), &'static str> {
+        let path = Profiles::path().ok_or("Couldn't find a home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|_| "Couldn't create the data directory")?;
+        }
+        fs::write(path, self.to_lines()).map_err(|_| "Couldn't write the profiles")
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Profile> {
+        self.entries.iter().find(|profile| profile.name == name)
+    }
+
+    // Stores `profile`, replacing any with the same name, and moves it to
+    // the front so it's picked next time.
+    pub fn select(&mut self, profile: Profile) {
+        self.entries.retain(|entry| entry.name != profile.name);
+        self.entries.insert(0, profile);
+    }
+
+    pub fn update(&mut self, profile: Profile) {
+        match self
+            .entries
+            .iter()
+            .position(|entry| entry.name == profile.name)
+        {
+            Some(index) => self.entries[index] = profile,
+            None => self.entries.push(profile),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_profile_round_trips_through_a_line() {
+        let profile = Profile {
+            keybindings: Keybindings::Vim,
+            difficulty: Difficulty::Hard,
+            board_width: 10,
+            board_height: 10,
+            achievements: vec![Achievement::Flawless, Achievement::Veteran],
+            ..Profile::new("Ada")
+        };
+        assert_eq!(
+            profile.to_line(),
+            "Ada\tVim\tdefault\tHard\t10x10\tFlawless,Veteran"
+        );
+        assert_eq!(Profile::from_line(&profile.to_line()), Ok(profile));
+        let profile = Profile::new("Bob");
+        assert_eq!(Profile::from_line(&profile.to_line()), Ok(profile));
+    }
+
+    #[test]
+    fn test_from_line_rejects_bad_profiles() {
+        assert!(Profile::from_line("").is_err());
+        assert!(Profile::from_line("\tWASD\tdefault\tHard\t8x8").is_err());
+        assert!(Profile::from_line("Ada\tMouse\tdefault\tHard\t8x8").is_err());
+        assert!(Profile::from_line("Ada\tWASD\tneon\tHard\t8x8").is_err());
+        assert!(Profile::from_line("Ada\tWASD\tdefault\tBrutal\t8x8").is_err());
+        assert!(Profile::from_line("Ada\tWASD\tdefault\tHard\tbig").is_err());
+        assert!(Profile::from_line("Ada\tWASD\tdefault\tHard\t8x8\tLucky").is_err());
+    }
+
+    #[test]
+    fn test_next_board_size_wraps_around() {
+        let mut profile = Profile::new("Ada");
+        let mut sizes = vec![];
+        for _ in 0..BOARD_SIZES.len() {
+            profile.next_board_size();
+            sizes.push((profile.board_width, profile.board_height));
+        }
+        assert_eq!(sizes, vec![(10, 10), (12, 12), (8, 8)]);
+    }
+
+    #[test]
+    fn test_unlock_returns_only_new_achievements() {
+        let mut profile = Profile::new("Ada");
+        assert_eq!(
+            profile.unlock(&[Achievement::Flawless]),
+            vec![Achievement::Flawless]
+        );
+        assert_eq!(
+            profile.unlock(&[Achievement::Flawless, Achievement::Admiral]),
+            vec![Achievement::Admiral]
+        );
+        assert_eq!(
+            profile.achievements,
+            vec![Achievement::Flawless, Achievement::Admiral]
+        );
+    }
+
+    #[test]
+    fn test_select_moves_the_profile_to_the_front() {
+        let mut profiles = Profiles::from_lines(
+            "Ada\tWASD\tdefault\tNormal\t8x8\nBob\tArrows\tdefault\tEasy\t8x8\n",
+        );
+        let bob = Profile {
+            difficulty: Difficulty::Hard,
+            ..profiles.find("Bob").unwrap().clone()
+        };
+        profiles.select(bob);
+        let names: Vec<&str> = profiles.entries.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, vec!["Bob", "Ada"]);
+        assert_eq!(profiles.entries[0].difficulty, Difficulty::Hard);
+    }
+
+    #[test]
+    fn test_from_lines_skips_damaged_profiles() {
+        let profiles = Profiles::from_lines("Ada\tWASD\tdefault\tNormal\t8x8\ngarbage\n");
+        assert_eq!(profiles.entries.len(), 1);
+        assert_eq!(profiles.to_lines(), "Ada\tWASD\tdefault\tNormal\t8x8\t\n");
+    }
+}
@@ -0,0 +1,205 @@
+use crate::models::{
+    Attack, AttackResult, Board, Coordinates, Faction, Game, Heading, MineEffect, Placeable, Play,
+    Strike, Turn, Weapon,
+};
+
+// One line of the plain text protocol spoken by scripts and external
+// engines.
+#[derive(Debug, PartialEq)]
+pub enum Command {
+    Place(Placeable, Coordinates, Heading),
+    Auto,
+    Fire(Weapon, Coordinates),
+    Target(Faction),
+    Quit,
+}
+
+impl Command {
+    pub fn parse(board: &Board, line: &str) -> Result<Command, &'static str> {
+        let cell = |name: &str| {
+            Coordinates::from_name(name)
+                .filter(|cell| board.contains(cell))
+                .ok_or("not a cell on the board")
+        };
+        let line = line.trim().to_lowercase();
+        let words: Vec<&str> = line.split_whitespace().collect();
+        match words.as_slice() {
+            ["place", "mine", name] => {
+                Ok(Command::Place(Placeable::Mine, cell(name)?, Heading::East))
+            }
+            ["place", length, name, heading] => {
+                let length = length.parse().map_err(|_| "not a ship length")?;
+                let heading = match *heading {
+                    "east" => Heading::East,
+                    "south" => Heading::South,
+                    _ => return Err("ships face east or south"),
+                };
+                Ok(Command::Place(
+                    Placeable::Ship(length),
+                    cell(name)?,
+                    heading,
+                ))
+            }
+            ["auto"] => Ok(Command::Auto),
+            ["fire", name] => Ok(Command::Fire(Weapon::Shot, cell(name)?)),
+            ["fire", weapon, name] => Ok(Command::Fire(
+                Weapon::parse(weapon).ok_or("no such weapon")?,
+                cell(name)?,
+            )),
+            ["target", name] => Faction::parse(name)
+                .map(Command::Target)
+                .ok_or("no such fleet"),
+            ["quit"] => Ok(Command::Quit),
+            _ => Err("unknown command"),
+        }
+    }
+}
+
+// One line per shot, like "blue red sunk C4 3": who fired, at whom, what
+// happened and where.
+fn report_attack(game: &Game, shooter: Faction, target: Faction, attack: &Attack) -> String {
+    let prefix = format!("{} {}", shooter.short_name(), target.short_name());
+    let cell = attack.coordinates.name();
+    match attack.result {
+        AttackResult::Hit => {
+            let fleet = game.fleet(target);
+            let sunk = fleet.ships.iter().find(|ship| {
+                ship.get_segment_coordinates().contains(&attack.coordinates)
+                    && fleet.is_ship_sunk(ship)
+            });
+            match sunk {
+                Some(ship) => format!("{} sunk {} {}", prefix, cell, ship.length),
+                None => format!("{} hit {}", prefix, cell),
+            }
+        }
+        AttackResult::Miss => format!("{} miss {}", prefix, cell),
+        AttackResult::Mine(MineEffect::Reveal(revealed)) => {
+            format!("{} mine {} reveal {}", prefix, cell, revealed.name())
+        }
+        AttackResult::Mine(MineEffect::LoseTurn) => {
+            format!("{} mine {} lose-turn", prefix, cell)
+        }
+    }
+}
+
+// What happened on `turn`, as protocol lines. There's always at least one,
+// so a script waiting on a reply never hangs.
+pub fn report(game: &Game, turn: &Turn) -> Vec<String> {
+    let shooter = turn.faction;
+    match &turn.play {
+        Play::Fire {
+            target,
+            strike: Strike::Ping(ping),
+            ..
+        } => vec![format!(
+            "{} {} ping {} {}",
+            shooter.short_name(),
+            target.short_name(),
+            ping.origin.name(),
+            if ping.found { "contact" } else { "nothing" }
+        )],
+        // A torpedo that ran off the board without finding anything.
+        Play::Fire {
+            weapon,
+            target,
+            strike: Strike::Attacks(attacks),
+        } if attacks.is_empty() => vec![format!(
+            "{} {} {} nothing",
+            shooter.short_name(),
+            target.short_name(),
+            weapon.name().to_lowercase()
+        )],
+        Play::Fire {
+            target,
+            strike: Strike::Attacks(attacks),
+            ..
+        } => attacks
+            .iter()
+            .map(|attack| report_attack(game, shooter, *target, attack))
+            .collect(),
+        Play::Move => vec![format!("{} move", shooter.short_name())],
+        Play::LostTurn => vec![format!("{} skip", shooter.short_name())],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Ship;
+
+    #[test]
+    fn test_parse_commands() {
+        let board = Board::new(8, 8);
+        let cell = |name| Coordinates::from_name(name).unwrap();
+        assert_eq!(
+            Command::parse(&board, "place 2 a1 east"),
+            Ok(Command::Place(
+                Placeable::Ship(2),
+                cell("A1"),
+                Heading::East
+            ))
+        );
+        assert_eq!(
+            Command::parse(&board, "place mine H8"),
+            Ok(Command::Place(Placeable::Mine, cell("H8"), Heading::East))
+        );
+        assert_eq!(
+            Command::parse(&board, "fire c4"),
+            Ok(Command::Fire(Weapon::Shot, cell("C4")))
+        );
+        assert_eq!(
+            Command::parse(&board, "fire Torpedo C4"),
+            Ok(Command::Fire(Weapon::Torpedo, cell("C4")))
+        );
+        assert_eq!(
+            Command::parse(&board, "target green"),
+            Ok(Command::Target(Faction::Green))
+        );
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_commands() {
+        let board = Board::new(8, 8);
+        assert_eq!(
+            Command::parse(&board, "fire i1"),
+            Err("not a cell on the board")
+        );
+        assert_eq!(
+            Command::parse(&board, "place 2 a1 west"),
+            Err("ships face east or south")
+        );
+        assert_eq!(
+            Command::parse(&board, "fire laser a1"),
+            Err("no such weapon")
+        );
+        assert_eq!(Command::parse(&board, "dance"), Err("unknown command"));
+    }
+
+    #[test]
+    fn test_report_shots() {
+        let mut game = Game {
+            active_player: Faction::Red,
+            ..Default::default()
+        };
+        game.place_ship(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 2))
+            .unwrap();
+        game.active_player = Faction::Blue;
+        game.launch(Weapon::Shot, Coordinates { x: 0, y: 0 })
+            .unwrap();
+        let turn = game.history.last().unwrap().clone();
+        assert_eq!(report(&game, &turn), vec!["blue red hit A1"]);
+        game.launch(Weapon::Shot, Coordinates { x: 1, y: 0 })
+            .unwrap();
+        let turn = game.history.last().unwrap().clone();
+        assert_eq!(report(&game, &turn), vec!["blue red sunk A2 2"]);
+    }
+
+    #[test]
+    fn test_report_a_torpedo_that_misses() {
+        let mut game = Game::default();
+        game.launch(Weapon::Torpedo, Coordinates { x: 0, y: 2 })
+            .unwrap();
+        let turn = game.history.last().unwrap().clone();
+        assert_eq!(report(&game, &turn), vec!["blue red torpedo nothing"]);
+    }
+}
@@ -0,0 +1,165 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::Records;
+
+// Rating every newcomer starts from.
+const INITIAL_RATING: f64 = 1500.0;
+// How far a single game can move a rating.
+const K_FACTOR: f64 = 32.0;
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Rating {
+    pub name: String,
+    pub rating: f64,
+    pub games: u32,
+}
+
+// Elo ratings for everyone who has played, human or bot.
+#[derive(Debug, Default)]
+pub struct Ratings {
+    pub entries: Vec<Rating>,
+}
+
+impl Ratings {
+    fn path() -> Option<PathBuf> {
+        Records::data_dir().map(|dir| dir.join("ratings"))
+    }
+
+    // Ratings are kept one per line as tab separated name, rating and
+    // games played. Unreadable lines are skipped.
+    pub fn from_lines(lines: &str) -> Ratings {
+        let mut ratings = Ratings::default();
+        for line in lines.lines() {
+            let fields: Vec<&str> = line.trim_end().split('\t').collect();
+            if let [name, rating, games] = fields.as_slice() {
+                if let (Ok(rating), Ok(games)) = (rating.parse(), games.parse()) {
+                    ratings.entries.push(Rating {
+                        name: name.to_string(),
+                        rating,
+                        games,
+                    });
+                }
+            }
+        }
+        ratings
+    }
+
+    pub fn to_lines(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{}\t{:.1}\t{}\n", entry.name, entry.rating, entry.games))
+            .collect()
+    }
+
+    pub fn load() -> Ratings {
+        match Ratings::path().map(fs::read_to_string) {
+            Some(Ok(lines)) => Ratings::from_lines(&lines),
+            _ => Ratings::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), &'static str> {
+        let path = Ratings::path().ok_or("Couldn't find a home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|_| "Couldn't create the data directory")?;
+        }
+        fs::write(path, self.to_lines()).map_err(|_| "Couldn't write the ratings")
+    }
+
+    pub fn rating(&self, name: &str) -> f64 {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name)
+            .map_or(INITIAL_RATING, |entry| entry.rating)
+    }
+
+    fn entry_mut(&mut self, name: &str) -> &mut Rating {
+        match self.entries.iter().position(|entry| entry.name == name) {
+            Some(index) => &mut self.entries[index],
+            None => {
+                self.entries.push(Rating {
+                    name: name.to_string(),
+                    rating: INITIAL_RATING,
+                    games: 0,
+                });
+                self.entries.last_mut().unwrap()
+            }
+        }
+    }
+
+    // Settles a game as a win for `winner` over each of `losers`.
+    // Everyone's changes are worked out from the ratings they came in
+    // with.
+    pub fn record(&mut self, winner: &str, losers: &[String]) {
+        let losers: Vec<&String> = losers.iter().filter(|name| *name != winner).collect();
+        let winner_rating = self.rating(winner);
+        let mut winner_change = 0.0;
+        for loser in losers.iter() {
+            let loser_rating = self.rating(loser);
+            let expected = 1.0 / (1.0 + 10f64.powf((loser_rating - winner_rating) / 400.0));
+            let change = K_FACTOR * (1.0 - expected);
+            winner_change += change;
+            let entry = self.entry_mut(loser);
+            entry.rating -= change;
+            entry.games += 1;
+        }
+        if !losers.is_empty() {
+            let entry = self.entry_mut(winner);
+            entry.rating += winner_change;
+            entry.games += 1;
+        }
+    }
+
+    // Everyone, best first.
+    pub fn leaderboard(&self) -> Vec<Rating> {
+        let mut entries = self.entries.clone();
+        entries.sort_by(|a, b| b.rating.partial_cmp(&a.rating).unwrap());
+        entries
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_newcomers_start_at_the_initial_rating() {
+        assert_eq!(Ratings::default().rating("Ada"), INITIAL_RATING);
+    }
+
+    #[test]
+    fn test_record_between_equals() {
+        let mut ratings = Ratings::default();
+        ratings.record("Ada", &["Bot (Hard)".to_string()]);
+        assert_eq!(ratings.rating("Ada"), 1516.0);
+        assert_eq!(ratings.rating("Bot (Hard)"), 1484.0);
+        assert!(ratings.entries.iter().all(|entry| entry.games == 1));
+    }
+
+    #[test]
+    fn test_upsets_move_ratings_further() {
+        let mut ratings = Ratings::from_lines("Ada\t1700.0\t10\nBob\t1300.0\t10\n");
+        ratings.record("Bob", &["Ada".to_string()]);
+        assert!(ratings.rating("Bob") > 1329.0);
+        assert!(ratings.rating("Ada") < 1671.0);
+        assert_eq!(ratings.rating("Ada") + ratings.rating("Bob"), 3000.0);
+    }
+
+    #[test]
+    fn test_record_ignores_playing_yourself() {
+        let mut ratings = Ratings::default();
+        ratings.record("Bot (Hard)", &["Bot (Hard)".to_string()]);
+        assert!(ratings.entries.is_empty());
+    }
+
+    #[test]
+    fn test_ratings_round_trip_through_lines() {
+        let mut ratings = Ratings::default();
+        ratings.record("Ada", &["Bob".to_string(), "Cy".to_string()]);
+        let loaded = Ratings::from_lines(&ratings.to_lines());
+        assert_eq!(loaded.entries, ratings.entries);
+        assert_eq!(loaded.leaderboard()[0].name, "Ada");
+        assert_eq!(Ratings::from_lines("junk\n").entries.len(), 0);
+    }
+}
@@ -0,0 +1,292 @@
+use std::env;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use crate::models::Difficulty;
+
+// The outcome of one finished game, from the player's side.
+#[derive(Debug, PartialEq, Clone)]
+pub struct GameRecord {
+    // The profile that played it.
+    pub profile: String,
+    pub won: bool,
+    pub difficulty: Difficulty,
+    pub shots: u16,
+    pub hits: u16,
+    pub turns: u16,
+    pub duration: Duration,
+}
+
+impl GameRecord {
+    pub fn accuracy(&self) -> u16 {
+        percentage(u64::from(self.hits), u64::from(self.shots))
+    }
+
+    // Records are kept one per line as tab separated fields: profile,
+    // result, difficulty, shots, hits, turns and seconds played.
+    pub fn to_line(&self) -> String {
+        format!(
+            "{}\t{}\t{}\t{}\t{}\t{}\t{}",
+            self.profile,
+            if self.won { "win" } else { "loss" },
+            self.difficulty.name(),
+            self.shots,
+            self.hits,
+            self.turns,
+            self.duration.as_secs()
+        )
+    }
+
+    pub fn from_line(line: &str) -> Result<GameRecord, &'static str> {
+        let fields: Vec<&str> = line.trim_end().split('\t').collect();
+        let (profile, fields) = match fields.split_first() {
+            Some((profile, fields)) if fields.len() == 6 => (profile, fields),
+            _ => return Err("A game record has the wrong number of fields"),
+        };
+        let number = |field: &str| {
+            field
+                .parse::<u16>()
+                .map_err(|_| "Bad number in a game record")
+        };
+        Ok(GameRecord {
+            profile: profile.to_string(),
+            won: match fields[0] {
+                "win" => true,
+                "loss" => false,
+                _ => return Err("Unknown result in a game record"),
+            },
+            difficulty: Difficulty::parse(fields[1])
+                .ok_or("Unknown difficulty in a game record")?,
+            shots: number(fields[2])?,
+            hits: number(fields[3])?,
+            turns: number(fields[4])?,
+            duration: Duration::from_secs(
+                fields[5]
+                    .parse::<u64>()
+                    .map_err(|_| "Bad duration in a game record")?,
+            ),
+        })
+    }
+}
+
+// Totals over a set of games.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub struct Summary {
+    pub played: u32,
+    pub won: u32,
+    pub accuracy: u16,
+}
+
+impl Summary {
+    pub fn win_rate(&self) -> u16 {
+        percentage(u64::from(self.won), u64::from(self.played))
+    }
+}
+
+// `part` as a whole percentage of `whole`, or zero when there's nothing to
+// take a share of. Worked out wide enough that long histories can't
+// overflow.
+fn percentage(part: u64, whole: u64) -> u16 {
+    (part * 100).checked_div(whole).unwrap_or(0) as u16
+}
+
+// Every finished game, oldest first.
+#[derive(Debug, Default)]
+pub struct Records {
+    pub games: Vec<GameRecord>,
+}
+
+impl Records {
+    // Where records are kept: $RUSTBUCKETS_HOME if set, otherwise
+    // ~/.rustbuckets.
+    pub fn data_dir() -> Option<PathBuf> {
+        match env::var_os("RUSTBUCKETS_HOME") {
+            Some(dir) => Some(PathBuf::from(dir)),
+            None => env::var_os("HOME").map(|home| PathBuf::from(home).join(".rustbuckets")),
+        }
+    }
+
+    fn path() -> Option<PathBuf> {
+        Records::data_dir().map(|dir| dir.join("games"))
+    }
+
+    pub fn from_lines(lines: &str) -> Records {
+        // A damaged line shouldn't cost the player the rest of their
+        // history, so anything unreadable is skipped.
+        Records {
+            games: lines
+                .lines()
+                .filter_map(|line| GameRecord::from_line(line).ok())
+                .collect(),
+        }
+    }
+
+    pub fn load() -> Records {
+        match Records::path().map(fs::read_to_string) {
+            Some(Ok(lines)) => Records::from_lines(&lines),
+            _ => Records::default(),
+        }
+    }
+
+    // Just the games `profile` played.
+    pub fn for_profile(self, profile: &str) -> Records {
+        Records {
+            games: self
+                .games
+                .into_iter()
+                .filter(|game| game.profile == profile)
+                .collect(),
+        }
+    }
+
+    pub fn save(record: GameRecord) -> Result<(), &'static str> {
+        let path = Records::path().ok_or("Couldn't find a home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|_| "Couldn't create the data directory")?;
+        }
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map_err(|_| "Couldn't open the game records")?;
+        writeln!(file, "{}", record.to_line()).map_err(|_| "Couldn't write the game record")
+    }
+
+    pub fn summary(&self) -> Summary {
+        Records::summarise(self.games.iter())
+    }
+
+    pub fn summary_for(&self, difficulty: Difficulty) -> Summary {
+        Records::summarise(
+            self.games
+                .iter()
+                .filter(|game| game.difficulty == difficulty),
+        )
+    }
+
+    fn summarise<'a>(games: impl Iterator<Item = &'a GameRecord>) -> Summary {
+        let (mut summary, mut shots, mut hits) = (Summary::default(), 0u64, 0u64);
+        for game in games {
+            summary.played += 1;
+            summary.won += u32::from(game.won);
+            shots += u64::from(game.shots);
+            hits += u64::from(game.hits);
+        }
+        summary.accuracy = percentage(hits, shots);
+        summary
+    }
+
+    // The quickest win, with the hardest difficulty breaking ties.
+    pub fn best_game(&self) -> Option<GameRecord> {
+        self.games
+            .iter()
+            .filter(|game| game.won)
+            .min_by_key(|game| {
+                let rank = Difficulty::all()
+                    .iter()
+                    .position(|difficulty| *difficulty == game.difficulty);
+                (game.turns, std::cmp::Reverse(rank))
+            })
+            .cloned()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(won: bool, difficulty: Difficulty, turns: u16) -> GameRecord {
+        GameRecord {
+            profile: "Ada".to_string(),
+            won,
+            difficulty,
+            shots: 20,
+            hits: 10,
+            turns,
+            duration: Duration::from_secs(90),
+        }
+    }
+
+    #[test]
+    fn test_record_round_trips_through_a_line() {
+        let game = record(true, Difficulty::Hard, 24);
+        assert_eq!(game.to_line(), "Ada\twin\tHard\t20\t10\t24\t90");
+        assert_eq!(GameRecord::from_line(&game.to_line()), Ok(game.clone()));
+    }
+
+    #[test]
+    fn test_from_line_rejects_bad_records() {
+        assert!(GameRecord::from_line("").is_err());
+        assert!(GameRecord::from_line("win\tHard\t1\t1\t1\t1").is_err());
+        assert!(GameRecord::from_line("Ada\tdraw\tHard\t1\t1\t1\t1").is_err());
+        assert!(GameRecord::from_line("Ada\twin\tBrutal\t1\t1\t1\t1").is_err());
+        assert!(GameRecord::from_line("Ada\twin\tHard\tmany\t1\t1\t1").is_err());
+    }
+
+    #[test]
+    fn test_from_lines_skips_damaged_lines() {
+        let records = Records::from_lines("Ada\twin\tEasy\t20\t16\t20\t60\ngarbage\n");
+        assert_eq!(records.games.len(), 1);
+    }
+
+    #[test]
+    fn test_summaries() {
+        let records = Records {
+            games: vec![
+                record(true, Difficulty::Easy, 30),
+                record(false, Difficulty::Hard, 40),
+                record(true, Difficulty::Hard, 30),
+                record(true, Difficulty::Normal, 35),
+            ],
+        };
+        let summary = records.summary();
+        assert_eq!(summary.played, 4);
+        assert_eq!(summary.won, 3);
+        assert_eq!(summary.win_rate(), 75);
+        assert_eq!(summary.accuracy, 50);
+        assert_eq!(records.summary_for(Difficulty::Hard).win_rate(), 50);
+        assert_eq!(records.summary_for(Difficulty::Normal).played, 1);
+        assert_eq!(
+            records.best_game(),
+            Some(record(true, Difficulty::Hard, 30))
+        );
+        assert_eq!(Records::default().best_game(), None);
+    }
+
+    #[test]
+    fn test_for_profile_keeps_only_their_games() {
+        let theirs = GameRecord {
+            profile: "Grace".to_string(),
+            ..record(true, Difficulty::Easy, 20)
+        };
+        let records = Records {
+            games: vec![record(false, Difficulty::Hard, 40), theirs.clone()],
+        };
+        assert_eq!(records.for_profile("Grace").games, vec![theirs]);
+    }
+
+    #[test]
+    fn test_percentages_of_long_histories() {
+        let game = GameRecord {
+            shots: 60000,
+            hits: 45000,
+            ..record(true, Difficulty::Hard, 30)
+        };
+        assert_eq!(game.accuracy(), 75);
+        let records = Records {
+            games: vec![game.clone(); 1000],
+        };
+        let summary = records.summary();
+        assert_eq!((summary.played, summary.won), (1000, 1000));
+        assert_eq!(summary.win_rate(), 100);
+        assert_eq!(summary.accuracy, 75);
+        let summary = Summary {
+            played: 70000,
+            won: 35000,
+            accuracy: 0,
+        };
+        assert_eq!(summary.win_rate(), 50);
+    }
+}
@@ -0,0 +1,66 @@
+use std::cell::RefCell;
+
+use rand::{rngs::StdRng, thread_rng, Error, RngCore, SeedableRng};
+
+thread_local! {
+    // Set while playing seeded games, so they can be replayed exactly.
+    static SEEDED: RefCell<Option<StdRng>> = const { RefCell::new(None) };
+}
+
+// Makes every roll of the dice on this thread repeatable from `seed`
+// onwards, or truly random again given None.
+pub fn seed(seed: Option<u64>) {
+    SEEDED.with(|seeded| *seeded.borrow_mut() = seed.map(StdRng::seed_from_u64));
+}
+
+// Where the game gets its randomness: the seeded generator if there is
+// one, otherwise the thread's own.
+pub fn rng() -> GameRng {
+    GameRng
+}
+
+#[derive(Debug, Copy, Clone)]
+pub struct GameRng;
+
+impl GameRng {
+    fn with<T>(self, roll: impl FnOnce(&mut dyn RngCore) -> T) -> T {
+        SEEDED.with(|seeded| match seeded.borrow_mut().as_mut() {
+            Some(seeded) => roll(seeded),
+            None => roll(&mut thread_rng()),
+        })
+    }
+}
+
+impl RngCore for GameRng {
+    fn next_u32(&mut self) -> u32 {
+        self.with(|rng| rng.next_u32())
+    }
+
+    fn next_u64(&mut self) -> u64 {
+        self.with(|rng| rng.next_u64())
+    }
+
+    fn fill_bytes(&mut self, dest: &mut [u8]) {
+        self.with(|rng| rng.fill_bytes(dest))
+    }
+
+    fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), Error> {
+        self.with(|rng| rng.try_fill_bytes(dest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    #[test]
+    fn test_seeded_rolls_repeat() {
+        seed(Some(7));
+        let first: Vec<u16> = (0..8).map(|_| rng().gen_range(0, 100)).collect();
+        seed(Some(7));
+        let second: Vec<u16> = (0..8).map(|_| rng().gen_range(0, 100)).collect();
+        seed(None);
+        assert_eq!(first, second);
+    }
+}
@@ -0,0 +1,200 @@
+use rand::seq::SliceRandom;
+
+use crate::models::{rng, AttackResult, Board, Coordinates, Fleet, Heading, Ship};
+
+// Imagines `samples` whole fleets that fit everything a shooter knows
+// about `target`'s waters, and counts how many of them put a ship on each
+// cell, indexed by row then column. None if no fleet fits at all.
+//
+// The shooter is taken to know the terrain, every shot and radar sweep
+// made against the fleet, the segments mine blasts gave away, and which
+// ships have been sunk and where.
+pub fn heat_map(board: &Board, target: &Fleet, samples: usize) -> Option<Vec<u32>> {
+    let cells = usize::from(board.width) * usize::from(board.height);
+    let index = |coordinates: &Coordinates| {
+        usize::from(coordinates.y) * usize::from(board.width) + usize::from(coordinates.x)
+    };
+
+    let (sunk, afloat): (Vec<&Ship>, Vec<&Ship>) = target
+        .ships
+        .iter()
+        .partition(|ship| target.is_ship_sunk(ship));
+
+    // Cells no ship still afloat can be on.
+    let mut blocked = vec![false; cells];
+    for y in 0..board.height {
+        for x in 0..board.width {
+            let coordinates = Coordinates { x, y };
+            blocked[index(&coordinates)] = !board.terrain_at(&coordinates).is_navigable();
+        }
+    }
+    for ship in sunk.iter() {
+        for coordinates in ship.get_segment_coordinates().iter() {
+            blocked[index(coordinates)] = true;
+        }
+    }
+    for attack in target.attacks.iter() {
+        match attack.result {
+            AttackResult::Miss if !attack.stale => blocked[index(&attack.coordinates)] = true,
+            AttackResult::Mine(_) if !attack.stale => blocked[index(&attack.coordinates)] = true,
+            _ => {}
+        }
+    }
+    for ping in target.pings.iter().filter(|ping| !ping.found) {
+        for coordinates in ping.area().iter().filter(|cell| board.contains(cell)) {
+            blocked[index(coordinates)] = true;
+        }
+    }
+
+    // Cells some ship still afloat must be on.
+    let mut required: Vec<usize> = target
+        .attacks
+        .iter()
+        .filter(|attack| attack.result == AttackResult::Hit)
+        .map(|attack| index(&attack.coordinates))
+        .chain(target.revealed.iter().map(&index))
+        .filter(|cell| !blocked[*cell])
+        .collect();
+    required.sort_unstable();
+    required.dedup();
+    // Areas where radar found something.
+    let contacts: Vec<Vec<usize>> = target
+        .pings
+        .iter()
+        .filter(|ping| ping.found)
+        .map(|ping| {
+            ping.area()
+                .iter()
+                .filter(|cell| board.contains(cell))
+                .map(&index)
+                .collect()
+        })
+        .collect();
+
+    // Everywhere each ship still afloat could lie, on its own.
+    let placements = |length: u16| -> Vec<Vec<usize>> {
+        let mut placements = vec![];
+        for y in 0..board.height {
+            for x in 0..board.width {
+                for heading in [Heading::East, Heading::South].iter() {
+                    let ship = Ship::new(Coordinates { x, y }, *heading, length);
+                    let segments = ship.get_segment_coordinates();
+                    if segments.iter().all(|cell| board.contains(cell))
+                        && segments.iter().all(|cell| !blocked[index(cell)])
+                    {
+                        placements.push(segments.iter().map(&index).collect());
+                    }
+                }
+            }
+        }
+        placements
+    };
+    let fleet: Vec<Vec<Vec<usize>>> = afloat.iter().map(|ship| placements(ship.length)).collect();
+
+    let mut counts = vec![0; cells];
+    let mut fitted = false;
+    let mut occupied = vec![false; cells];
+    let mut order: Vec<usize> = (0..fleet.len()).collect();
+    for _ in 0..samples {
+        occupied.iter_mut().for_each(|cell| *cell = false);
+        order.shuffle(&mut rng());
+        let mut fits = true;
+        for ship in order.iter() {
+            let open: Vec<&Vec<usize>> = fleet[*ship]
+                .iter()
+                .filter(|segments| segments.iter().all(|cell| !occupied[*cell]))
+                .collect();
+            // Cover the known hits first, or this sample is all but sure
+            // to be thrown away.
+            let covering: Vec<&Vec<usize>> = open
+                .iter()
+                .copied()
+                .filter(|segments| {
+                    segments
+                        .iter()
+                        .any(|cell| required.contains(cell) && !occupied[*cell])
+                })
+                .collect();
+            let choice = if covering.is_empty() {
+                open.choose(&mut rng())
+            } else {
+                covering.choose(&mut rng())
+            };
+            match choice {
+                Some(segments) => segments.iter().for_each(|cell| occupied[*cell] = true),
+                None => {
+                    fits = false;
+                    break;
+                }
+            }
+        }
+        fits = fits
+            && required.iter().all(|cell| occupied[*cell])
+            && contacts
+                .iter()
+                .all(|area| area.iter().any(|cell| occupied[*cell]));
+        if !fits {
+            continue;
+        }
+        fitted = true;
+        for (cell, count) in counts.iter_mut().enumerate() {
+            if occupied[cell] {
+                *count += 1;
+            }
+        }
+    }
+    if fitted {
+        Some(counts)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Attack, Faction};
+
+    #[test]
+    fn test_heat_map_closes_in_on_a_hit() {
+        let mut fleet = Fleet::new(Faction::Red);
+        fleet
+            .ships
+            .push(Ship::new(Coordinates { x: 3, y: 3 }, Heading::East, 2));
+        fleet.attacks.push(Attack::with_result(
+            Coordinates { x: 3, y: 3 },
+            AttackResult::Hit,
+        ));
+        fleet.attacks.push(Attack::with_result(
+            Coordinates { x: 2, y: 3 },
+            AttackResult::Miss,
+        ));
+        let counts = heat_map(&Board::new(8, 8), &fleet, 200).unwrap();
+        // Every imagined destroyer covers the hit, and reaches one of its
+        // three open neighbours.
+        assert_eq!(counts[3 * 8 + 3], 200);
+        assert_eq!(counts[3 * 8 + 2], 0);
+        let neighbours = counts[3 * 8 + 4] + counts[2 * 8 + 3] + counts[4 * 8 + 3];
+        assert_eq!(neighbours, 200);
+        assert_eq!(counts.iter().sum::<u32>(), 400);
+    }
+
+    #[test]
+    fn test_heat_map_without_room_for_the_fleet() {
+        let mut fleet = Fleet::new(Faction::Red);
+        fleet
+            .ships
+            .push(Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 3));
+        for x in 0..2 {
+            for y in 0..2 {
+                if (x, y) != (0, 0) {
+                    fleet.attacks.push(Attack::with_result(
+                        Coordinates { x, y },
+                        AttackResult::Miss,
+                    ));
+                }
+            }
+        }
+        assert_eq!(heat_map(&Board::new(2, 2), &fleet, 50), None);
+    }
+}
@@ -33,6 +33,17 @@ impl Ship {
         segments
     }
 
+    // What a ship this long is called.
+    pub fn class(&self) -> &'static str {
+        match self.length {
+            5 => "carrier",
+            4 => "battleship",
+            3 => "cruiser",
+            2 => "destroyer",
+            _ => "patrol boat",
+        }
+    }
+
     pub fn default() -> Ship {
         Ship::new(Coordinates::default(), Heading::default(), 2)
     }
@@ -96,4 +107,11 @@ mod test {
         assert_eq!(segments[1].x, 1);
         assert_eq!(segments[1].y, 0);
     }
+
+    #[test]
+    fn test_class() {
+        let ship = Ship::new(Coordinates { x: 0, y: 0 }, Heading::East, 5);
+        assert_eq!(ship.class(), "carrier");
+        assert_eq!(Ship::default().class(), "destroyer");
+    }
 }
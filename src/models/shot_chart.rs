@@ -0,0 +1,176 @@
+use std::fs;
+use std::path::PathBuf;
+
+use crate::models::{Faction, Play, Records, Strike, Turn, Weapon};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Chart {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    // Shots fired at each cell, by row then column.
+    pub counts: Vec<u32>,
+}
+
+// Where each commander tends to aim, tallied over all their games, so
+// the computer can hide its fleet from their habits. Boards of different
+// sizes are charted separately.
+#[derive(Debug, Default)]
+pub struct ShotChart {
+    pub entries: Vec<Chart>,
+}
+
+impl ShotChart {
+    fn path() -> Option<PathBuf> {
+        Records::data_dir().map(|dir| dir.join("shots"))
+    }
+
+    // Charts are kept one per line as tab separated name, board size and
+    // comma separated counts. Unreadable lines are skipped.
+    pub fn from_lines(lines: &str) -> ShotChart {
+        let mut chart = ShotChart::default();
+        for line in lines.lines() {
+            let fields: Vec<&str> = line.trim_end().split('\t').collect();
+            let (name, size, counts) = match fields.as_slice() {
+                [name, size, counts] => (name, size, counts),
+                _ => continue,
+            };
+            let size: Option<(u16, u16)> = size
+                .split_once('x')
+                .and_then(|(width, height)| Some((width.parse().ok()?, height.parse().ok()?)));
+            let counts: Result<Vec<u32>, _> = counts.split(',').map(str::parse).collect();
+            if let (Some((width, height)), Ok(counts)) = (size, counts) {
+                if counts.len() == usize::from(width) * usize::from(height) {
+                    chart.entries.push(Chart {
+                        name: name.to_string(),
+                        width,
+                        height,
+                        counts,
+                    });
+                }
+            }
+        }
+        chart
+    }
+
+    pub fn to_lines(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| {
+                let counts: Vec<String> = entry.counts.iter().map(u32::to_string).collect();
+                format!(
+                    "{}\t{}x{}\t{}\n",
+                    entry.name,
+                    entry.width,
+                    entry.height,
+                    counts.join(",")
+                )
+            })
+            .collect()
+    }
+
+    pub fn load() -> ShotChart {
+        match ShotChart::path().map(fs::read_to_string) {
+            Some(Ok(lines)) => ShotChart::from_lines(&lines),
+            _ => ShotChart::default(),
+        }
+    }
+
+    pub fn save(&self) -> Result<(), &'static str> {
+        let path = ShotChart::path().ok_or("Couldn't find a home directory")?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).map_err(|_| "Couldn't create the data directory")?;
+        }
+        fs::write(path, self.to_lines()).map_err(|_| "Couldn't write the shot chart")
+    }
+
+    // Adds the cells `faction` picked for its shots in `history` to
+    // `name`'s chart.
+    pub fn record(
+        &mut self,
+        name: &str,
+        (width, height): (u16, u16),
+        history: &[Turn],
+        faction: Faction,
+    ) {
+        let index =
+            match self.entries.iter().position(|entry| {
+                entry.name == name && (entry.width, entry.height) == (width, height)
+            }) {
+                Some(index) => index,
+                None => {
+                    self.entries.push(Chart {
+                        name: name.to_string(),
+                        width,
+                        height,
+                        counts: vec![0; usize::from(width) * usize::from(height)],
+                    });
+                    self.entries.len() - 1
+                }
+            };
+        let chart = &mut self.entries[index];
+        for turn in history.iter().filter(|turn| turn.faction == faction) {
+            if let Play::Fire {
+                weapon: Weapon::Shot,
+                strike: Strike::Attacks(attacks),
+                ..
+            } = &turn.play
+            {
+                for attack in attacks.iter() {
+                    let (x, y) = (attack.coordinates.x, attack.coordinates.y);
+                    if x < width && y < height {
+                        chart.counts[usize::from(y) * usize::from(width) + usize::from(x)] += 1;
+                    }
+                }
+            }
+        }
+    }
+
+    // `name`'s shots on a board of this size, if they've played on one.
+    pub fn counts(&self, name: &str, width: u16, height: u16) -> Option<&[u32]> {
+        self.entries
+            .iter()
+            .find(|entry| entry.name == name && (entry.width, entry.height) == (width, height))
+            .map(|entry| entry.counts.as_slice())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Attack, AttackResult, Coordinates};
+
+    fn shot(x: u16, y: u16) -> Turn {
+        Turn::new(
+            Faction::Blue,
+            Play::Fire {
+                weapon: Weapon::Shot,
+                target: Faction::Red,
+                strike: Strike::Attacks(vec![Attack::with_result(
+                    Coordinates { x, y },
+                    AttackResult::Miss,
+                )]),
+            },
+        )
+    }
+
+    #[test]
+    fn test_record_tallies_shots_by_board_size() {
+        let mut chart = ShotChart::default();
+        let history = vec![shot(0, 0), shot(1, 1), shot(1, 1)];
+        chart.record("Ada", (2, 2), &history, Faction::Blue);
+        chart.record("Ada", (2, 2), &history[..1], Faction::Blue);
+        chart.record("Ada", (2, 2), &history, Faction::Red);
+        assert_eq!(chart.counts("Ada", 2, 2), Some(&[2, 0, 0, 2][..]));
+        assert_eq!(chart.counts("Ada", 3, 3), None);
+        assert_eq!(chart.counts("Bob", 2, 2), None);
+    }
+
+    #[test]
+    fn test_lines_round_trip() {
+        let lines = "Ada\t2x2\t2,0,0,2\nBob\t2x2\t1,2\nbroken line\n";
+        let chart = ShotChart::from_lines(lines);
+        assert_eq!(chart.entries.len(), 1);
+        assert_eq!(chart.to_lines(), "Ada\t2x2\t2,0,0,2\n");
+    }
+}
@@ -0,0 +1,60 @@
+use crate::models::{AttackResult, Faction, Play, Strike, Turn};
+
+// How a faction's game went, worked out from the game's history.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub struct Stats {
+    pub turns: u16,
+    pub shots: u16,
+    pub hits: u16,
+    pub longest_streak: u16,
+}
+
+impl Stats {
+    pub fn new(history: &[Turn], faction: Faction) -> Stats {
+        let mut stats = Stats::default();
+        let mut streak = 0;
+        for turn in history.iter().filter(|turn| turn.faction == faction) {
+            stats.turns += 1;
+            let attacks = match &turn.play {
+                Play::Fire {
+                    strike: Strike::Attacks(attacks),
+                    ..
+                } => attacks,
+                _ => continue,
+            };
+            for attack in attacks.iter() {
+                stats.shots += 1;
+                if attack.result == AttackResult::Hit {
+                    stats.hits += 1;
+                    streak += 1;
+                    stats.longest_streak = stats.longest_streak.max(streak);
+                } else {
+                    streak = 0;
+                }
+            }
+        }
+        stats
+    }
+
+    // Percentage of shots that found a ship.
+    pub fn accuracy(&self) -> u16 {
+        (u32::from(self.hits) * 100)
+            .checked_div(u32::from(self.shots))
+            .unwrap_or(0) as u16
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_accuracy_on_a_huge_board() {
+        let stats = Stats {
+            shots: 4000,
+            hits: 1000,
+            ..Stats::default()
+        };
+        assert_eq!(stats.accuracy(), 25);
+    }
+}
@@ -0,0 +1,8 @@
+use crate::models::{Attack, Ping};
+
+// Everything a single use of a weapon produced.
+#[derive(Debug, Clone)]
+pub enum Strike {
+    Attacks(Vec<Attack>),
+    Ping(Ping),
+}
@@ -0,0 +1,27 @@
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Terrain {
+    Water,
+    Island,
+    Reef,
+}
+
+impl Terrain {
+    pub fn from_char(c: char) -> Option<Terrain> {
+        match c {
+            '.' => Some(Terrain::Water),
+            '#' => Some(Terrain::Island),
+            '~' => Some(Terrain::Reef),
+            _ => None,
+        }
+    }
+
+    // Ships can only be placed on open water.
+    pub fn is_navigable(self) -> bool {
+        self == Terrain::Water
+    }
+
+    // Reefs sit below the waterline, so only islands stop a shot.
+    pub fn blocks_fire(self) -> bool {
+        self == Terrain::Island
+    }
+}
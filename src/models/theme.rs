@@ -0,0 +1,48 @@
+// How the game is coloured in.
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Theme {
+    #[default]
+    Default,
+    // Bright on black, for washed out screens and tired eyes.
+    HighContrast,
+    // Blues and yellows, so nothing hangs on telling red from green.
+    Colourblind,
+    // No colour at all; every state has its own glyph instead.
+    Monochrome,
+}
+
+impl Theme {
+    pub fn all() -> Vec<Theme> {
+        vec![
+            Theme::Default,
+            Theme::HighContrast,
+            Theme::Colourblind,
+            Theme::Monochrome,
+        ]
+    }
+
+    // Lower case, as they've always been written in the profiles file.
+    pub fn name(self) -> &'static str {
+        match self {
+            Theme::Default => "default",
+            Theme::HighContrast => "high-contrast",
+            Theme::Colourblind => "colourblind",
+            Theme::Monochrome => "monochrome",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Theme> {
+        Theme::all()
+            .into_iter()
+            .find(|theme| theme.name().eq_ignore_ascii_case(name))
+    }
+
+    pub fn next(self) -> Theme {
+        match self {
+            Theme::Default => Theme::HighContrast,
+            Theme::HighContrast => Theme::Colourblind,
+            Theme::Colourblind => Theme::Monochrome,
+            Theme::Monochrome => Theme::Default,
+        }
+    }
+}
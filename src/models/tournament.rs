@@ -0,0 +1,278 @@
+use crate::models::Difficulty;
+
+// Someone taking part in a tournament.
+#[derive(Debug, PartialEq, Clone)]
+pub enum Entrant {
+    Bot(Difficulty),
+    // The shell command that starts an external engine.
+    Engine(String),
+}
+
+impl Entrant {
+    // A difficulty names a built-in bot; anything else is taken to be an
+    // engine's command.
+    pub fn parse(spec: &str) -> Entrant {
+        match Difficulty::parse(spec) {
+            Some(difficulty) => Entrant::Bot(difficulty),
+            None => Entrant::Engine(spec.to_string()),
+        }
+    }
+
+    // What to call the entrant until an engine names itself.
+    pub fn name(&self) -> String {
+        match self {
+            Entrant::Bot(difficulty) => format!("Bot ({})", difficulty.name()),
+            Entrant::Engine(command) => format!("Engine ({})", command),
+        }
+    }
+}
+
+// How a round-robin went, pairing by pairing.
+#[derive(Debug)]
+pub struct Tournament {
+    pub names: Vec<String>,
+    // Where the dice started, so the whole thing can be replayed.
+    pub seed: u64,
+    // Seeds played by each pairing; every seed is played from both sides.
+    pub games: u32,
+    // wins[a][b] is how many games `a` won against `b`.
+    wins: Vec<Vec<u32>>,
+    draws: Vec<Vec<u32>>,
+    // Shots fired across every game each entrant won.
+    shots_to_win: Vec<u32>,
+}
+
+impl Tournament {
+    pub fn new(names: Vec<String>, seed: u64, games: u32) -> Tournament {
+        let count = names.len();
+        Tournament {
+            names,
+            seed,
+            games,
+            wins: vec![vec![0; count]; count],
+            draws: vec![vec![0; count]; count],
+            shots_to_win: vec![0; count],
+        }
+    }
+
+    // Every pair of entrants, each pair once.
+    pub fn pairings(&self) -> Vec<(usize, usize)> {
+        let count = self.names.len();
+        (0..count)
+            .flat_map(|a| (a + 1..count).map(move |b| (a, b)))
+            .collect()
+    }
+
+    pub fn record_win(&mut self, winner: usize, loser: usize, shots: u16) {
+        self.wins[winner][loser] += 1;
+        self.shots_to_win[winner] += u32::from(shots);
+    }
+
+    pub fn record_draw(&mut self, a: usize, b: usize) {
+        self.draws[a][b] += 1;
+        self.draws[b][a] += 1;
+    }
+
+    // Wins, losses and draws for `entrant` against `opponent`.
+    pub fn head_to_head(&self, entrant: usize, opponent: usize) -> (u32, u32, u32) {
+        (
+            self.wins[entrant][opponent],
+            self.wins[opponent][entrant],
+            self.draws[entrant][opponent],
+        )
+    }
+
+    // Wins, losses and draws for `entrant` across the tournament.
+    pub fn totals(&self, entrant: usize) -> (u32, u32, u32) {
+        (0..self.names.len()).fold((0, 0, 0), |(wins, losses, draws), opponent| {
+            let (w, l, d) = self.head_to_head(entrant, opponent);
+            (wins + w, losses + l, draws + d)
+        })
+    }
+
+    pub fn win_percentage(&self, entrant: usize) -> f64 {
+        let (wins, losses, draws) = self.totals(entrant);
+        match wins + losses + draws {
+            0 => 0.0,
+            games => f64::from(wins) * 100.0 / f64::from(games),
+        }
+    }
+
+    // None until the entrant has won a game.
+    pub fn average_shots_to_win(&self, entrant: usize) -> Option<f64> {
+        match self.totals(entrant).0 {
+            0 => None,
+            wins => Some(f64::from(self.shots_to_win[entrant]) / f64::from(wins)),
+        }
+    }
+
+    // One row per entrant, with a column for each opponent holding
+    // wins-losses-draws.
+    pub fn to_csv(&self) -> String {
+        let mut header = vec![
+            "entrant".to_string(),
+            "wins".to_string(),
+            "losses".to_string(),
+            "draws".to_string(),
+            "win_percentage".to_string(),
+            "average_shots_to_win".to_string(),
+        ];
+        header.extend(
+            self.names
+                .iter()
+                .map(|name| csv_field(&format!("vs {}", name))),
+        );
+        let mut lines = vec![header.join(",")];
+        for (entrant, name) in self.names.iter().enumerate() {
+            let (wins, losses, draws) = self.totals(entrant);
+            let mut row = vec![
+                csv_field(name),
+                wins.to_string(),
+                losses.to_string(),
+                draws.to_string(),
+                format!("{:.1}", self.win_percentage(entrant)),
+                self.average_shots_to_win(entrant)
+                    .map(|shots| format!("{:.1}", shots))
+                    .unwrap_or_default(),
+            ];
+            for opponent in 0..self.names.len() {
+                row.push(match self.head_to_head(entrant, opponent) {
+                    _ if opponent == entrant => "".to_string(),
+                    (w, l, d) => format!("{}-{}-{}", w, l, d),
+                });
+            }
+            lines.push(row.join(","));
+        }
+        lines.join("\n") + "\n"
+    }
+
+    pub fn to_json(&self) -> String {
+        let entrants: Vec<String> = self
+            .names
+            .iter()
+            .enumerate()
+            .map(|(entrant, name)| {
+                let (wins, losses, draws) = self.totals(entrant);
+                let against: Vec<String> = (0..self.names.len())
+                    .filter(|opponent| *opponent != entrant)
+                    .map(|opponent| {
+                        let (w, l, d) = self.head_to_head(entrant, opponent);
+                        format!(
+                            "{{\"opponent\": {}, \"wins\": {}, \"losses\": {}, \"draws\": {}}}",
+                            json_string(&self.names[opponent]),
+                            w,
+                            l,
+                            d
+                        )
+                    })
+                    .collect();
+                format!(
+                    "    {{\"name\": {}, \"wins\": {}, \"losses\": {}, \"draws\": {}, \
+                     \"win_percentage\": {:.1}, \"average_shots_to_win\": {}, \
+                     \"against\": [{}]}}",
+                    json_string(name),
+                    wins,
+                    losses,
+                    draws,
+                    self.win_percentage(entrant),
+                    self.average_shots_to_win(entrant)
+                        .map(|shots| format!("{:.1}", shots))
+                        .unwrap_or_else(|| "null".to_string()),
+                    against.join(", ")
+                )
+            })
+            .collect();
+        format!(
+            "{{\n  \"seed\": {},\n  \"seeds_per_pairing\": {},\n  \"entrants\": [\n{}\n  ]\n}}\n",
+            self.seed,
+            self.games,
+            entrants.join(",\n")
+        )
+    }
+}
+
+// Quotes a CSV field if it needs it.
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+fn json_string(text: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in text.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tournament() -> Tournament {
+        let names = vec![
+            "Bot (Easy)".to_string(),
+            "Bot (Hard)".to_string(),
+            "Engine (python3 \"bot, v2.py\")".to_string(),
+        ];
+        let mut tournament = Tournament::new(names, 42, 2);
+        tournament.record_win(1, 0, 30);
+        tournament.record_win(1, 0, 40);
+        tournament.record_win(0, 2, 50);
+        tournament.record_draw(1, 2);
+        tournament
+    }
+
+    #[test]
+    fn test_entrant_parse() {
+        assert_eq!(Entrant::parse("hard"), Entrant::Bot(Difficulty::Hard));
+        assert_eq!(
+            Entrant::parse("./bot --fast"),
+            Entrant::Engine("./bot --fast".to_string())
+        );
+    }
+
+    #[test]
+    fn test_pairings_cover_everyone_once() {
+        assert_eq!(tournament().pairings(), vec![(0, 1), (0, 2), (1, 2)]);
+    }
+
+    #[test]
+    fn test_totals_and_averages() {
+        let tournament = tournament();
+        assert_eq!(tournament.head_to_head(0, 1), (0, 2, 0));
+        assert_eq!(tournament.totals(1), (2, 0, 1));
+        assert_eq!(tournament.win_percentage(0), 100.0 / 3.0);
+        assert_eq!(tournament.average_shots_to_win(1), Some(35.0));
+        assert_eq!(tournament.average_shots_to_win(2), None);
+    }
+
+    #[test]
+    fn test_csv_report() {
+        let csv = tournament().to_csv();
+        let lines: Vec<&str> = csv.lines().collect();
+        assert_eq!(
+            lines[0],
+            "entrant,wins,losses,draws,win_percentage,average_shots_to_win,\
+             vs Bot (Easy),vs Bot (Hard),\"vs Engine (python3 \"\"bot, v2.py\"\")\""
+        );
+        assert_eq!(lines[2], "Bot (Hard),2,0,1,66.7,35.0,2-0-0,,0-0-1");
+    }
+
+    #[test]
+    fn test_json_report_escapes_names() {
+        let json = tournament().to_json();
+        assert!(json.contains("\"seed\": 42"));
+        assert!(json.contains("\"name\": \"Engine (python3 \\\"bot, v2.py\\\")\""));
+        assert!(json.contains("\"average_shots_to_win\": null"));
+    }
+}
@@ -0,0 +1,27 @@
+use crate::models::{Faction, Strike, Weapon};
+
+// What a commander did with their turn.
+#[derive(Debug, Clone)]
+pub enum Play {
+    Fire {
+        weapon: Weapon,
+        target: Faction,
+        strike: Strike,
+    },
+    Move,
+    // Sat out to pay for hitting a mine.
+    LostTurn,
+}
+
+// One entry in the game's history.
+#[derive(Debug, Clone)]
+pub struct Turn {
+    pub faction: Faction,
+    pub play: Play,
+}
+
+impl Turn {
+    pub fn new(faction: Faction, play: Play) -> Turn {
+        Turn { faction, play }
+    }
+}
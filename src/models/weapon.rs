@@ -0,0 +1,38 @@
+#[derive(Debug, Default, PartialEq, Copy, Clone)]
+pub enum Weapon {
+    // A single shell at one cell.
+    #[default]
+    Shot,
+    // Reports whether any ship is inside a 3x3 area without damaging it.
+    Radar,
+    // Hits a three cell segment of a row.
+    Airstrike,
+    // Runs east along a row from the aimed cell until it hits something.
+    Torpedo,
+}
+
+impl Weapon {
+    pub fn all() -> Vec<Weapon> {
+        vec![
+            Weapon::Shot,
+            Weapon::Radar,
+            Weapon::Airstrike,
+            Weapon::Torpedo,
+        ]
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Weapon::Shot => "Shot",
+            Weapon::Radar => "Radar",
+            Weapon::Airstrike => "Airstrike",
+            Weapon::Torpedo => "Torpedo",
+        }
+    }
+
+    pub fn parse(name: &str) -> Option<Weapon> {
+        Weapon::all()
+            .into_iter()
+            .find(|weapon| weapon.name().eq_ignore_ascii_case(name))
+    }
+}
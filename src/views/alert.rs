@@ -1,8 +1,11 @@
-use crate::models::{Alert, Coordinates, Level};
+use crate::{
+    models::{Alert, Coordinates, Level},
+    views::palette,
+};
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{clear, color, style};
+use termion::{clear, style};
 
 pub struct AlertView {
     pub origin: Coordinates,
@@ -19,9 +22,7 @@ impl AlertView {
     }
 
     fn draw_horizontal_edge(&self) -> String {
-        let line: String = (1..48).map(|_| "-").collect();
-
-        format!("{}", line)
+        (1..48).map(|_| "-").collect()
     }
 
     pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
@@ -33,13 +34,8 @@ impl AlertView {
             Level::Error => "ERROR",
         };
 
-        // WTF
-        let color = match self.model.level {
-            Level::Info => color::Fg(color::Cyan).to_string(),
-            Level::Success => color::Fg(color::Green).to_string(),
-            Level::Warning => color::Fg(color::Yellow).to_string(),
-            Level::Error => color::Fg(color::Red).to_string(),
-        };
+        let palette = palette();
+        let color = palette.alert(&self.model.level);
 
         write!(
             stdout,
@@ -54,12 +50,13 @@ impl AlertView {
 
         write!(
             stdout,
-            "{}{}{}{}{}{}{}{}",
+            "{}{}{}{}{}: {}{}{}{}",
             Goto(self.origin.x, self.origin.y),
             color,
             self.draw_horizontal_edge(),
             Goto(self.origin.x, self.origin.y + 1),
-            format!("{}: {}", label, self.model.content),
+            label,
+            self.model.content,
             Goto(self.origin.x, self.origin.y + 2),
             self.draw_horizontal_edge(),
             style::Reset
@@ -0,0 +1,61 @@
+use crate::{
+    models::{Arsenal, Coordinates, Weapon},
+    views::palette,
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+pub struct ArsenalView {
+    origin: Coordinates,
+    model: Arsenal,
+    selected: Weapon,
+}
+
+impl ArsenalView {
+    pub fn new(origin: Coordinates, model: Arsenal, selected: Weapon) -> ArsenalView {
+        ArsenalView {
+            origin,
+            model,
+            selected,
+        }
+    }
+
+    pub fn update(self, model: Arsenal, selected: Weapon) -> ArsenalView {
+        ArsenalView {
+            model,
+            selected,
+            ..self
+        }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let weapons = [
+            Weapon::Shot,
+            Weapon::Radar,
+            Weapon::Airstrike,
+            Weapon::Torpedo,
+        ];
+        let palette = palette();
+        for (index, weapon) in weapons.iter().enumerate() {
+            let marker = if *weapon == self.selected { ">" } else { " " };
+            let remaining = match self.model.remaining(*weapon) {
+                Some(count) => format!(" x{}", count),
+                None => "".to_string(),
+            };
+            // Padded out rather than cleared to the end of the line, which
+            // would wipe any board beside this one.
+            let line = format!("{} [{}] {}{}", marker, index + 1, weapon.name(), remaining);
+            write!(
+                stdout,
+                "{}{}{:<20}{}",
+                Goto(self.origin.x, self.origin.y + index as u16),
+                palette.text,
+                line,
+                style::Reset
+            )
+            .unwrap();
+        }
+    }
+}
@@ -1,27 +1,43 @@
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
 use crate::{
     models::{Attack, AttackResult, Coordinates},
-    views::utils::translate_game_coords_to_board_coords,
+    views::{palette, utils::translate_game_coords_to_board_coords},
 };
 
 pub struct AttackView {
     origin: Coordinates,
     model: Attack,
+    // Whether this was the last shot fired on the board.
+    latest: bool,
 }
 
 impl AttackView {
     pub fn new(origin: Coordinates, model: Attack) -> AttackView {
-        AttackView { origin, model }
+        AttackView {
+            origin,
+            model,
+            latest: false,
+        }
+    }
+
+    pub fn latest(self) -> AttackView {
+        AttackView {
+            latest: true,
+            ..self
+        }
     }
 
     pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let palette = palette();
         let symbol = match self.model.result {
             AttackResult::Hit => "X",
+            AttackResult::Miss if self.model.stale => palette.stale_glyph,
             AttackResult::Miss => "^",
+            AttackResult::Mine(_) => "*",
         };
 
         let board_coords = translate_game_coords_to_board_coords(self.model.coordinates);
@@ -30,12 +46,23 @@ impl AttackView {
             y: board_coords.y + self.origin.y,
         };
 
+        // Stale misses are faded out since the cell may no longer be empty.
+        let (colour, marks) = if self.latest {
+            (palette.latest, palette.latest_marks)
+        } else if self.model.stale {
+            (palette.stale, (" ", " "))
+        } else {
+            (palette.attack, (" ", " "))
+        };
+
         write!(
             stdout,
-            "{}{} {} {}",
+            "{}{}{}{}{}{}",
             Goto(screen_coords.x, screen_coords.y),
-            color::Bg(color::Blue),
+            colour,
+            marks.0,
             symbol,
+            marks.1,
             style::Reset
         )
         .unwrap();
@@ -1,9 +1,12 @@
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
-use crate::models::{Board, Coordinates};
+use crate::{
+    models::{Board, Coordinates, Terrain},
+    views::{palette, utils::translate_game_coords_to_board_coords},
+};
 
 pub struct BoardView {
     pub origin: Coordinates,
@@ -15,49 +18,108 @@ impl BoardView {
         BoardView { origin, model }
     }
 
+    #[allow(dead_code)]
     pub fn update(self, model: Board) -> BoardView {
         BoardView { model, ..self }
     }
 
-    fn render_latitude_line(&self, stdout: &mut RawTerminal<Stdout>) {
+    fn render_latitude_line(&self, stdout: &mut RawTerminal<Stdout>, row: u16) {
         let mut output = "+".to_string();
-        for _ in 0..self.model.height {
+        for _ in 0..self.model.width {
             output.push_str("---+");
         }
         write!(
             stdout,
-            "{}{}{}{}\n\r",
-            color::Fg(color::White),
-            color::Bg(color::Blue),
+            "{}{}{}{}",
+            Goto(self.origin.x, self.origin.y + row),
+            palette().water,
             output,
             style::Reset
         )
         .unwrap();
     }
 
-    fn render_longitude_line(&self, stdout: &mut RawTerminal<Stdout>) {
+    fn render_longitude_line(&self, stdout: &mut RawTerminal<Stdout>, row: u16) {
         let mut output = "|".to_string();
         for _ in 0..self.model.width {
             output.push_str("   |");
         }
         write!(
             stdout,
-            "{}{}{}{}\n\r",
-            color::Fg(color::White),
-            color::Bg(color::Blue),
+            "{}{}{}{}",
+            Goto(self.origin.x, self.origin.y + row),
+            palette().water,
             output,
             style::Reset
         )
         .unwrap();
     }
 
+    fn render_terrain(&self, stdout: &mut RawTerminal<Stdout>) {
+        let palette = palette();
+        for y in 0..self.model.height {
+            for x in 0..self.model.width {
+                let coordinates = Coordinates { x, y };
+                let board_coords = translate_game_coords_to_board_coords(coordinates);
+                let goto = Goto(
+                    board_coords.x + self.origin.x,
+                    board_coords.y + self.origin.y,
+                );
+                match self.model.terrain_at(&coordinates) {
+                    // Shade every other cell, like a chequered chart.
+                    Terrain::Water if !palette.shallows.is_empty() && (x + y) % 2 == 1 => {
+                        write!(stdout, "{}{}   {}", goto, palette.shallows, style::Reset).unwrap()
+                    }
+                    Terrain::Water => {}
+                    Terrain::Island => {
+                        write!(stdout, "{}{}###{}", goto, palette.island, style::Reset).unwrap()
+                    }
+                    Terrain::Reef => {
+                        write!(stdout, "{}{}~~~{}", goto, palette.reef, style::Reset).unwrap()
+                    }
+                }
+            }
+        }
+    }
+
+    // Column numbers along the top and row letters down the left, so cells
+    // can be called out by name.
+    fn render_labels(&self, stdout: &mut RawTerminal<Stdout>) {
+        let palette = palette();
+        for x in 0..self.model.width {
+            write!(
+                stdout,
+                "{}{}{:^3}{}",
+                Goto(self.origin.x + x * 4 + 1, self.origin.y.saturating_sub(1)),
+                palette.text,
+                x + 1,
+                style::Reset
+            )
+            .unwrap();
+        }
+        for y in 0..self.model.height {
+            let name = Coordinates { x: 0, y }.name();
+            write!(
+                stdout,
+                "{}{}{}{}",
+                Goto(self.origin.x.saturating_sub(2), self.origin.y + y * 2 + 1),
+                palette.text,
+                &name[..1],
+                style::Reset
+            )
+            .unwrap();
+        }
+    }
+
     pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
-        write!(stdout, "{}", Goto(self.origin.x, self.origin.y)).unwrap();
-        for _ in 1..self.model.height + 1 {
-            self.render_latitude_line(stdout);
-            self.render_longitude_line(stdout);
+        // Boards can sit side by side, so each line is placed explicitly
+        // rather than relying on the cursor wrapping to column one.
+        for y in 0..self.model.height {
+            self.render_latitude_line(stdout, y * 2);
+            self.render_longitude_line(stdout, y * 2 + 1);
         }
-        self.render_latitude_line(stdout);
-        write!(stdout, "\n\r").unwrap();
+        self.render_latitude_line(stdout, self.model.height * 2);
+        self.render_terrain(stdout);
+        self.render_labels(stdout);
     }
 }
@@ -0,0 +1,44 @@
+use crate::{
+    models::{Clock, Coordinates},
+    views::palette,
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+pub struct ClockView {
+    origin: Coordinates,
+    model: Clock,
+}
+
+impl ClockView {
+    pub fn new(origin: Coordinates, model: Clock) -> ClockView {
+        ClockView { origin, model }
+    }
+
+    pub fn update(self, model: Clock) -> ClockView {
+        ClockView { model, ..self }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        // Round up so the clock only reads zero once time is actually up.
+        let remaining = self.model.remaining();
+        let seconds = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        let palette = palette();
+        let colour = if seconds <= 5 {
+            palette.error
+        } else {
+            palette.text
+        };
+        write!(
+            stdout,
+            "{}{}{:<20}{}",
+            Goto(self.origin.x, self.origin.y),
+            colour,
+            format!("Time: {}s", seconds),
+            style::Reset
+        )
+        .unwrap();
+    }
+}
@@ -1,11 +1,11 @@
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
 use crate::{
     models::{Coordinates, Cursor},
-    views::utils::translate_game_coords_to_board_coords,
+    views::{palette, utils::translate_game_coords_to_board_coords},
 };
 
 #[derive(Copy, Clone)]
@@ -29,7 +29,7 @@ impl CursorView {
             stdout,
             "{}{}[ ]{}",
             Goto(screen_coords.x, screen_coords.y),
-            color::Bg(color::Blue),
+            palette().cursor,
             style::Reset
         )
         .unwrap()
@@ -0,0 +1,44 @@
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+use crate::{
+    models::Coordinates,
+    views::{palette, utils::translate_game_coords_to_board_coords},
+};
+
+// A shell closing in on a cell, drawn a frame at a time.
+const FRAMES: [&str; 4] = [" . ", " o ", " O ", "(O)"];
+
+pub struct IncomingView {
+    origin: Coordinates,
+    model: Coordinates,
+    frame: usize,
+}
+
+impl IncomingView {
+    pub fn new(origin: Coordinates, model: Coordinates, frame: usize) -> IncomingView {
+        IncomingView {
+            origin,
+            model,
+            frame,
+        }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let board_coords = translate_game_coords_to_board_coords(self.model);
+        write!(
+            stdout,
+            "{}{}{}{}",
+            Goto(
+                board_coords.x + self.origin.x,
+                board_coords.y + self.origin.y
+            ),
+            palette().incoming,
+            FRAMES[self.frame % FRAMES.len()],
+            style::Reset
+        )
+        .unwrap();
+    }
+}
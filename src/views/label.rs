@@ -1,8 +1,11 @@
-use crate::models::{Coordinates, Label};
+use crate::{
+    models::{Coordinates, Label},
+    views::palette,
+};
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
 pub struct LabelView {
     origin: Coordinates,
@@ -19,7 +22,7 @@ impl LabelView {
             stdout,
             "{}{}{}{}",
             Goto(self.origin.x, self.origin.y),
-            color::Fg(color::White),
+            palette().text,
             self.model.content,
             style::Reset
         )
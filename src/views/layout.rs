@@ -0,0 +1,298 @@
+use crate::models::{Board, Coordinates};
+
+// Room left of each board for its row letters.
+const ROW_LABELS: u16 = 2;
+// Room right of the player's board for scores, the clock and weapons.
+const SIDEBAR: u16 = 22;
+// Alerts are 48 columns wide and 3 rows tall.
+const ALERT_WIDTH: u16 = 48;
+const ALERT_HEIGHT: u16 = 3;
+// The end screen's result and the rating beside it.
+const RESULTS_WIDTH: u16 = 68;
+// The game's title and a blank line sit above everything else.
+const TOP: u16 = 3;
+
+// Where everything sits on screen for a game played on `board`, sized to
+// the terminal. When there's room, the player's board sits in one row with
+// every opponent's. Otherwise the opponents fill rows along the top, as
+// many to a row as fit, with the player's board underneath.
+pub struct Layout {
+    board_width: u16,
+    board_height: u16,
+    opponents: usize,
+    terminal: (u16, u16),
+    side_by_side: bool,
+    // Opponent boards to a row, when stacked.
+    columns: usize,
+}
+
+impl Layout {
+    pub fn new(board: &Board, opponents: usize) -> Layout {
+        // Without a terminal to ask, assume there's room for anything.
+        let terminal = termion::terminal_size().unwrap_or((u16::MAX, u16::MAX));
+        Layout::with_size(board, opponents, terminal)
+    }
+
+    pub fn with_size(board: &Board, opponents: usize, terminal: (u16, u16)) -> Layout {
+        let mut layout = Layout {
+            board_width: board.width * 4 + 1,
+            board_height: board.height * 2 + 1,
+            opponents,
+            terminal,
+            side_by_side: true,
+            columns: opponents.max(1),
+        };
+        if !layout.fits() {
+            layout.side_by_side = false;
+            layout.columns = (terminal.0 / layout.column_width()).max(1) as usize;
+            layout.columns = layout.columns.min(opponents.max(1));
+        }
+        layout
+    }
+
+    // Each opponent gets its board plus room for its labels and a scores
+    // column.
+    fn column_width(&self) -> u16 {
+        ROW_LABELS + self.board_width + 14
+    }
+
+    fn player_column_width(&self) -> u16 {
+        ROW_LABELS + self.board_width + 2 + SIDEBAR
+    }
+
+    // A board with its title and column numbers, and a gap below.
+    fn row_height(&self) -> u16 {
+        self.board_height + 4
+    }
+
+    fn rows(&self, boards: usize, columns: usize) -> u16 {
+        boards.div_ceil(columns) as u16
+    }
+
+    pub fn terminal(&self) -> (u16, u16) {
+        self.terminal
+    }
+
+    // The smallest terminal the game screens fit in, as laid out.
+    pub fn required(&self) -> (u16, u16) {
+        if self.side_by_side {
+            (
+                self.player_column_width() + self.opponents as u16 * self.column_width(),
+                self.alert().y + ALERT_HEIGHT - 1,
+            )
+        } else {
+            (
+                (self.columns as u16 * self.column_width())
+                    .max(self.player_column_width())
+                    .max(ALERT_WIDTH),
+                self.player_board().y + self.board_height - 1,
+            )
+        }
+    }
+
+    pub fn fits(&self) -> bool {
+        let (width, height) = self.required();
+        width <= self.terminal.0 && height <= self.terminal.1
+    }
+
+    pub fn opponent_title(&self, index: usize) -> Coordinates {
+        if self.side_by_side {
+            Coordinates {
+                x: 1 + self.player_column_width() + index as u16 * self.column_width(),
+                y: TOP,
+            }
+        } else {
+            Coordinates {
+                x: 1 + (index % self.columns) as u16 * self.column_width(),
+                y: TOP + (index / self.columns) as u16 * self.row_height(),
+            }
+        }
+    }
+
+    // Boards sit below their title and a row of column numbers.
+    pub fn opponent_board(&self, index: usize) -> Coordinates {
+        let title = self.opponent_title(index);
+        Coordinates {
+            x: title.x + ROW_LABELS,
+            y: title.y + 2,
+        }
+    }
+
+    pub fn opponent_scores(&self, index: usize) -> Coordinates {
+        let board = self.opponent_board(index);
+        Coordinates {
+            x: board.x + self.board_width + 2,
+            y: board.y - 4,
+        }
+    }
+
+    pub fn alert(&self) -> Coordinates {
+        let rows = if self.side_by_side {
+            1
+        } else {
+            self.rows(self.opponents.max(1), self.columns)
+        };
+        Coordinates {
+            x: 1,
+            y: TOP + rows * self.row_height(),
+        }
+    }
+
+    pub fn player_title(&self) -> Coordinates {
+        Coordinates {
+            x: 1,
+            y: if self.side_by_side {
+                TOP
+            } else {
+                self.alert().y + 4
+            },
+        }
+    }
+
+    pub fn player_board(&self) -> Coordinates {
+        Coordinates {
+            x: 1 + ROW_LABELS,
+            y: self.player_title().y + 2,
+        }
+    }
+
+    // Beside the player's board: scores, then the clock and weapons.
+    fn player_sidebar(&self) -> u16 {
+        self.player_board().x + self.board_width + 2
+    }
+
+    pub fn player_scores(&self) -> Coordinates {
+        Coordinates {
+            x: self.player_sidebar(),
+            y: self.player_board().y - 4,
+        }
+    }
+
+    pub fn clock(&self) -> Coordinates {
+        Coordinates {
+            x: self.player_sidebar(),
+            y: self.player_board().y + 3,
+        }
+    }
+
+    pub fn arsenal(&self) -> Coordinates {
+        Coordinates {
+            x: self.player_sidebar(),
+            y: self.player_board().y + 5,
+        }
+    }
+
+    // The end screen leads with the outcome and the rating beside it, lists
+    // every fleet's results, then shows all the boards in rows, as many to
+    // a row as fit.
+    fn result_columns(&self) -> usize {
+        let fleets = self.opponents + 1;
+        ((self.terminal.0 / self.column_width()).max(1) as usize).min(fleets)
+    }
+
+    // Under the title and the instructions.
+    pub fn outcome(&self) -> Coordinates {
+        Coordinates { x: 1, y: 5 }
+    }
+
+    pub fn rating(&self) -> Coordinates {
+        Coordinates {
+            x: 40,
+            ..self.outcome()
+        }
+    }
+
+    pub fn result(&self, index: usize) -> Coordinates {
+        Coordinates {
+            x: 1,
+            y: self.outcome().y + 2 + index as u16,
+        }
+    }
+
+    // Straight under the results, in case the game couldn't be recorded.
+    pub fn unsaved(&self) -> Coordinates {
+        self.result(self.opponents + 1)
+    }
+
+    pub fn result_title(&self, index: usize) -> Coordinates {
+        let columns = self.result_columns();
+        Coordinates {
+            x: 1 + (index % columns) as u16 * self.column_width(),
+            y: self.unsaved().y + 1 + (index / columns) as u16 * self.row_height(),
+        }
+    }
+
+    pub fn result_board(&self, index: usize) -> Coordinates {
+        let title = self.result_title(index);
+        Coordinates {
+            x: title.x + ROW_LABELS,
+            y: title.y + 2,
+        }
+    }
+
+    // Newly unlocked achievements are announced under the boards.
+    pub fn unlocked(&self, index: usize) -> Coordinates {
+        let rows = self.rows(self.opponents + 1, self.result_columns());
+        Coordinates {
+            x: 1,
+            y: self.unsaved().y + rows * self.row_height() + index as u16 * ALERT_HEIGHT,
+        }
+    }
+
+    // The smallest terminal the end screen fits in, with `alerts` of them
+    // under the boards.
+    pub fn required_for_results(&self, alerts: usize) -> (u16, u16) {
+        let rows = self.rows(self.opponents + 1, self.result_columns());
+        (
+            (self.result_columns() as u16 * self.column_width())
+                .max(ALERT_WIDTH)
+                .max(RESULTS_WIDTH),
+            self.unsaved().y - 1 + rows * self.row_height() + alerts as u16 * ALERT_HEIGHT,
+        )
+    }
+
+    pub fn fits_results(&self, alerts: usize) -> bool {
+        let (width, height) = self.required_for_results(alerts);
+        width <= self.terminal.0 && height <= self.terminal.1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_boards_sit_side_by_side_when_wide_enough() {
+        let layout = Layout::with_size(&Board::new(10, 10), 1, (200, 60));
+        assert!(layout.fits());
+        assert_eq!(layout.player_title().y, layout.opponent_title(0).y);
+        assert!(layout.opponent_title(0).x > layout.player_board().x + 41);
+    }
+
+    #[test]
+    fn test_boards_stack_when_narrow() {
+        let layout = Layout::with_size(&Board::new(10, 10), 3, (120, 80));
+        assert!(layout.fits());
+        assert_eq!(layout.opponent_title(1).y, layout.opponent_title(0).y);
+        assert!(layout.opponent_title(2).y > layout.opponent_title(0).y);
+        assert!(layout.player_title().y > layout.alert().y);
+    }
+
+    #[test]
+    fn test_end_screen_grows_with_the_fleets() {
+        let duel = Layout::with_size(&Board::new(8, 8), 1, (200, 60));
+        let melee = Layout::with_size(&Board::new(8, 8), 3, (200, 60));
+        assert_eq!(duel.result(0), melee.result(0));
+        assert!(duel.unsaved().y > duel.result(1).y);
+        assert!(duel.result_title(0).y < melee.result_title(0).y);
+        assert!(duel.required_for_results(0).1 < melee.required_for_results(0).1);
+    }
+
+    #[test]
+    fn test_small_terminal_does_not_fit() {
+        let layout = Layout::with_size(&Board::new(10, 10), 1, (40, 20));
+        assert!(!layout.fits());
+        let (width, height) = layout.required();
+        assert!(width > 40 && height > 20);
+    }
+}
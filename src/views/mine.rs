@@ -0,0 +1,38 @@
+use crate::{
+    models::{Coordinates, Mine},
+    views::{palette, utils::translate_game_coords_to_board_coords},
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+pub struct MineView {
+    origin: Coordinates,
+    model: Mine,
+}
+
+impl MineView {
+    pub fn new(origin: Coordinates, model: Mine) -> MineView {
+        MineView { origin, model }
+    }
+
+    pub fn update(self, model: Mine) -> MineView {
+        MineView { model, ..self }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let board_coords = translate_game_coords_to_board_coords(self.model.coordinates);
+        write!(
+            stdout,
+            "{}{} * {}",
+            Goto(
+                board_coords.x + self.origin.x,
+                board_coords.y + self.origin.y
+            ),
+            palette().mine,
+            style::Reset
+        )
+        .unwrap();
+    }
+}
@@ -1,13 +1,25 @@
 mod alert;
+mod arsenal;
 mod attack;
 mod board;
+mod clock;
 mod cursor;
+mod incoming;
 mod label;
+mod layout;
+mod mine;
+mod palette;
+mod ping;
+mod reveal;
 mod scores;
 mod ship;
+mod stats;
+mod too_small;
 mod utils;
 
 pub use self::{
-    alert::AlertView, attack::AttackView, board::BoardView, cursor::CursorView, label::LabelView,
-    scores::ScoresView, ship::ShipView, utils::*,
+    alert::AlertView, arsenal::ArsenalView, attack::AttackView, board::BoardView, clock::ClockView,
+    cursor::CursorView, incoming::IncomingView, label::LabelView, layout::Layout, mine::MineView,
+    palette::palette, palette::set_theme, ping::PingView, reveal::RevealView, scores::ScoresView,
+    ship::ShipView, stats::StatsView, too_small::TooSmallView, utils::cell_at,
 };
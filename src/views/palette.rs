@@ -0,0 +1,287 @@
+use std::env;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use termion::color::{self, AnsiValue, Bg, Color, Fg, Rgb};
+
+use crate::models::{Level, Theme};
+
+// The theme every view draws with, as a position in Theme::all(). It's
+// set whenever the player might have changed, rather than handed to every
+// view.
+static THEME: AtomicUsize = AtomicUsize::new(0);
+static DEPTH: AtomicUsize = AtomicUsize::new(0);
+
+// How many colours the terminal can show.
+#[derive(Debug, PartialEq, Copy, Clone)]
+enum Depth {
+    // The eight colours, and their bright versions, every terminal has.
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+impl Depth {
+    fn all() -> [Depth; 3] {
+        [Depth::Basic, Depth::Ansi256, Depth::TrueColor]
+    }
+
+    // Terminals don't say outright, but COLORTERM and TERM are the usual
+    // hints.
+    fn detect(colorterm: Option<&str>, term: Option<&str>) -> Depth {
+        match (colorterm, term) {
+            (Some("truecolor"), _) | (Some("24bit"), _) => Depth::TrueColor,
+            (_, Some(term)) if term.contains("256color") => Depth::Ansi256,
+            _ => Depth::Basic,
+        }
+    }
+}
+
+pub fn set_theme(theme: Theme) {
+    let depth = Depth::detect(
+        env::var("COLORTERM").ok().as_deref(),
+        env::var("TERM").ok().as_deref(),
+    );
+    let depth = Depth::all()
+        .iter()
+        .position(|candidate| *candidate == depth)
+        .unwrap_or(0);
+    DEPTH.store(depth, Ordering::SeqCst);
+
+    // Setting NO_COLOR turns colour off, whatever the profile says.
+    let theme = match env::var_os("NO_COLOR") {
+        Some(value) if !value.is_empty() => Theme::Monochrome,
+        _ => theme,
+    };
+    let index = Theme::all()
+        .iter()
+        .position(|candidate| *candidate == theme)
+        .unwrap_or(0);
+    THEME.store(index, Ordering::SeqCst);
+}
+
+fn current_theme() -> Theme {
+    Theme::all()[THEME.load(Ordering::SeqCst)]
+}
+
+fn current_depth() -> Depth {
+    Depth::all()[DEPTH.load(Ordering::SeqCst)]
+}
+
+// The richest version of a background colour the terminal can show: an
+// RGB value, one of the 256 colours, or one of the basic ones.
+fn bg<C: Color>(depth: Depth, rgb: (u8, u8, u8), value: u8, basic: C) -> String {
+    match depth {
+        Depth::TrueColor => Bg(Rgb(rgb.0, rgb.1, rgb.2)).to_string(),
+        Depth::Ansi256 => Bg(AnsiValue(value)).to_string(),
+        Depth::Basic => Bg(basic).to_string(),
+    }
+}
+
+// Escape codes, and where colour isn't enough, glyphs, for everything
+// drawn on screen.
+pub struct Palette {
+    // Board lines and open water.
+    pub water: String,
+    pub island: String,
+    pub reef: String,
+    // Titles, labels and figures.
+    pub text: String,
+    // Every other cell of open water is a shade lighter, where the
+    // terminal has the colours for it.
+    pub shallows: String,
+    pub ship: String,
+    pub ship_glyph: &'static str,
+    pub damaged: String,
+    pub sunk: String,
+    pub sunk_glyph: &'static str,
+    pub mine: String,
+    pub attack: String,
+    // Misses that may not be empty any more.
+    pub stale: String,
+    pub stale_glyph: &'static str,
+    // The most recent shot on a board, and what goes either side of it.
+    pub latest: String,
+    pub latest_marks: (&'static str, &'static str),
+    pub reveal: String,
+    pub ping: String,
+    pub incoming: String,
+    pub cursor: String,
+    pub info: String,
+    pub success: String,
+    pub warning: String,
+    pub error: String,
+}
+
+impl Palette {
+    pub fn alert(&self, level: &Level) -> &str {
+        match level {
+            Level::Info => &self.info,
+            Level::Success => &self.success,
+            Level::Warning => &self.warning,
+            Level::Error => &self.error,
+        }
+    }
+}
+
+pub fn palette() -> Palette {
+    let depth = current_depth();
+    match current_theme() {
+        Theme::Default => {
+            let sea = bg(depth, (0, 55, 140), 25, color::Blue);
+            Palette {
+                water: format!("{}{}", Fg(color::White), sea),
+                shallows: match depth {
+                    Depth::Basic => String::new(),
+                    _ => bg(depth, (0, 75, 170), 26, color::Blue),
+                },
+                island: format!("{}{}", Fg(color::Black), Bg(color::Green)),
+                reef: format!("{}{}", Fg(color::Cyan), sea),
+                text: Fg(color::White).to_string(),
+                ship: bg(depth, (170, 30, 30), 124, color::Red),
+                ship_glyph: "   ",
+                damaged: format!(
+                    "{}{}",
+                    Fg(color::Yellow),
+                    bg(depth, (230, 110, 20), 208, color::Red)
+                ),
+                sunk: format!(
+                    "{}{}",
+                    Fg(color::Black),
+                    bg(depth, (100, 100, 100), 242, color::LightBlack)
+                ),
+                sunk_glyph: " X ",
+                mine: format!("{}{}", Fg(color::Black), Bg(color::Yellow)),
+                attack: format!("{}{}", sea, Fg(color::Reset)),
+                stale: format!("{}{}", sea, Fg(color::LightBlack)),
+                stale_glyph: "^",
+                latest: format!(
+                    "{}{}",
+                    Fg(color::Black),
+                    bg(depth, (255, 215, 0), 220, color::Yellow)
+                ),
+                latest_marks: (" ", " "),
+                reveal: Bg(color::Red).to_string(),
+                ping: format!("{}{}", Fg(color::Magenta), sea),
+                incoming: format!("{}{}", Fg(color::Yellow), Bg(color::Red)),
+                cursor: sea,
+                info: Fg(color::Cyan).to_string(),
+                success: Fg(color::Green).to_string(),
+                warning: Fg(color::Yellow).to_string(),
+                error: Fg(color::Red).to_string(),
+            }
+        }
+        // Flat colours: shading would only take the edge off.
+        Theme::HighContrast => Palette {
+            water: format!("{}{}", Fg(color::LightWhite), Bg(color::Black)),
+            shallows: String::new(),
+            island: format!("{}{}", Fg(color::Black), Bg(color::LightGreen)),
+            reef: format!("{}{}", Fg(color::LightCyan), Bg(color::Black)),
+            text: Fg(color::LightWhite).to_string(),
+            ship: Bg(color::LightWhite).to_string(),
+            ship_glyph: "   ",
+            damaged: format!("{}{}", Fg(color::Black), Bg(color::LightRed)),
+            sunk: format!("{}{}", Fg(color::White), Bg(color::LightBlack)),
+            sunk_glyph: " X ",
+            mine: format!("{}{}", Fg(color::Black), Bg(color::LightYellow)),
+            attack: format!("{}{}", Bg(color::Black), Fg(color::LightYellow)),
+            stale: format!("{}{}", Bg(color::Black), Fg(color::LightBlack)),
+            stale_glyph: "^",
+            latest: format!("{}{}", Fg(color::Black), Bg(color::LightYellow)),
+            latest_marks: (" ", " "),
+            reveal: format!("{}{}", Fg(color::Black), Bg(color::LightRed)),
+            ping: format!("{}{}", Fg(color::LightMagenta), Bg(color::Black)),
+            incoming: format!("{}{}", Fg(color::LightYellow), Bg(color::LightRed)),
+            cursor: format!("{}{}", Fg(color::Black), Bg(color::LightWhite)),
+            info: Fg(color::LightCyan).to_string(),
+            success: Fg(color::LightGreen).to_string(),
+            warning: Fg(color::LightYellow).to_string(),
+            error: Fg(color::LightRed).to_string(),
+        },
+        Theme::Colourblind => {
+            let sea = bg(depth, (0, 55, 140), 25, color::Blue);
+            Palette {
+                water: format!("{}{}", Fg(color::White), sea),
+                shallows: match depth {
+                    Depth::Basic => String::new(),
+                    _ => bg(depth, (0, 75, 170), 26, color::Blue),
+                },
+                island: format!("{}{}", Fg(color::White), Bg(color::LightBlack)),
+                reef: format!("{}{}", Fg(color::Cyan), sea),
+                text: Fg(color::White).to_string(),
+                ship: bg(depth, (230, 170, 0), 178, color::Yellow),
+                ship_glyph: "   ",
+                damaged: format!(
+                    "{}{}",
+                    Fg(color::Black),
+                    bg(depth, (240, 240, 240), 255, color::LightWhite)
+                ),
+                sunk: format!(
+                    "{}{}",
+                    Fg(color::Black),
+                    bg(depth, (100, 100, 100), 242, color::LightBlack)
+                ),
+                sunk_glyph: " X ",
+                mine: format!("{}{}", Fg(color::White), Bg(color::Magenta)),
+                attack: format!("{}{}", sea, Fg(color::Reset)),
+                stale: format!("{}{}", sea, Fg(color::LightBlack)),
+                stale_glyph: "^",
+                latest: format!(
+                    "{}{}",
+                    Fg(color::Black),
+                    bg(depth, (0, 200, 255), 45, color::Cyan)
+                ),
+                latest_marks: (" ", " "),
+                reveal: format!("{}{}", Fg(color::Black), Bg(color::LightYellow)),
+                ping: format!("{}{}", Fg(color::LightYellow), sea),
+                incoming: format!("{}{}", Fg(color::Black), Bg(color::LightYellow)),
+                cursor: sea,
+                info: Fg(color::Cyan).to_string(),
+                success: Fg(color::LightBlue).to_string(),
+                warning: Fg(color::Yellow).to_string(),
+                error: Fg(color::Magenta).to_string(),
+            }
+        }
+        Theme::Monochrome => Palette {
+            water: String::new(),
+            shallows: String::new(),
+            island: String::new(),
+            reef: String::new(),
+            text: String::new(),
+            ship: String::new(),
+            ship_glyph: " = ",
+            damaged: String::new(),
+            sunk: String::new(),
+            sunk_glyph: "XXX",
+            mine: String::new(),
+            attack: String::new(),
+            stale: String::new(),
+            stale_glyph: "-",
+            latest: String::new(),
+            latest_marks: ("(", ")"),
+            reveal: String::new(),
+            ping: String::new(),
+            incoming: String::new(),
+            cursor: String::new(),
+            info: String::new(),
+            success: String::new(),
+            warning: String::new(),
+            error: String::new(),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detect_depth() {
+        assert_eq!(
+            Depth::detect(Some("truecolor"), Some("xterm-256color")),
+            Depth::TrueColor
+        );
+        assert_eq!(Depth::detect(Some("24bit"), None), Depth::TrueColor);
+        assert_eq!(Depth::detect(None, Some("xterm-256color")), Depth::Ansi256);
+        assert_eq!(Depth::detect(None, Some("xterm")), Depth::Basic);
+        assert_eq!(Depth::detect(None, None), Depth::Basic);
+    }
+}
@@ -0,0 +1,51 @@
+use crate::{
+    models::{Board, Coordinates, Ping},
+    views::{palette, utils::translate_game_coords_to_board_coords},
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+pub struct PingView {
+    origin: Coordinates,
+    model: Ping,
+    cells: Vec<Coordinates>,
+}
+
+impl PingView {
+    pub fn new(origin: Coordinates, model: Ping, board: &Board) -> PingView {
+        // Only shade the open water the sweep covered.
+        let cells = model
+            .area()
+            .into_iter()
+            .filter(|coords| board.contains(coords))
+            .filter(|coords| board.terrain_at(coords).is_navigable())
+            .collect();
+        PingView {
+            origin,
+            model,
+            cells,
+        }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let symbol = if self.model.found { " o " } else { " . " };
+        let palette = palette();
+        for coordinates in self.cells.iter() {
+            let board_coords = translate_game_coords_to_board_coords(*coordinates);
+            write!(
+                stdout,
+                "{}{}{}{}",
+                Goto(
+                    board_coords.x + self.origin.x,
+                    board_coords.y + self.origin.y
+                ),
+                palette.ping,
+                symbol,
+                style::Reset
+            )
+            .unwrap();
+        }
+    }
+}
@@ -0,0 +1,35 @@
+use crate::{
+    models::Coordinates,
+    views::{palette, utils::translate_game_coords_to_board_coords},
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+// Marks an enemy ship segment that a mine blast gave away.
+pub struct RevealView {
+    origin: Coordinates,
+    model: Coordinates,
+}
+
+impl RevealView {
+    pub fn new(origin: Coordinates, model: Coordinates) -> RevealView {
+        RevealView { origin, model }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let board_coords = translate_game_coords_to_board_coords(self.model);
+        write!(
+            stdout,
+            "{}{} ? {}",
+            Goto(
+                board_coords.x + self.origin.x,
+                board_coords.y + self.origin.y
+            ),
+            palette().reveal,
+            style::Reset
+        )
+        .unwrap();
+    }
+}
@@ -1,8 +1,11 @@
-use crate::models::{Coordinates, Scores};
+use crate::{
+    models::{Coordinates, Scores},
+    views::palette,
+};
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
 pub struct ScoresView {
     origin: Coordinates,
@@ -23,7 +26,7 @@ impl ScoresView {
             stdout,
             "{}{}{}Hits: {}{}Misses: {}{}",
             Goto(self.origin.x, self.origin.y + 4),
-            color::Fg(color::White),
+            palette().text,
             Goto(self.origin.x, self.origin.y + 5),
             self.model.hits,
             Goto(self.origin.x, self.origin.y + 6),
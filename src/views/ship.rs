@@ -1,20 +1,43 @@
 use crate::{
-    models::{Coordinates, Ship},
-    views::utils::translate_game_coords_to_board_coords,
+    models::{Coordinates, Fleet, Ship},
+    views::{palette, utils::translate_game_coords_to_board_coords},
 };
 use std::io::{Stdout, Write};
 use termion::cursor::Goto;
 use termion::raw::RawTerminal;
-use termion::{color, style};
+use termion::style;
 
 pub struct ShipView {
     origin: Coordinates,
     model: Ship,
+    // Segments that have been hit.
+    hits: Vec<Coordinates>,
+    sunk: bool,
 }
 
 impl ShipView {
     pub fn new(origin: Coordinates, model: Ship) -> ShipView {
-        ShipView { origin, model }
+        ShipView {
+            origin,
+            model,
+            hits: vec![],
+            sunk: false,
+        }
+    }
+
+    // Shows the damage `fleet`, the ship's own, has taken.
+    pub fn with_damage(self, fleet: &Fleet) -> ShipView {
+        let hits = self
+            .model
+            .get_segment_coordinates()
+            .into_iter()
+            .filter(|coordinates| fleet.is_hit(coordinates))
+            .collect();
+        ShipView {
+            hits,
+            sunk: fleet.is_ship_sunk(&self.model),
+            ..self
+        }
     }
 
     pub fn update(self, model: Ship) -> ShipView {
@@ -22,18 +45,27 @@ impl ShipView {
     }
 
     pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        let palette = palette();
         for coordinates in self.model.get_segment_coordinates().into_iter() {
             let board_coords = translate_game_coords_to_board_coords(coordinates);
             let screen_coords = Coordinates {
                 x: board_coords.x + self.origin.x,
                 y: board_coords.y + self.origin.y,
             };
+            let (colour, glyph) = if self.sunk {
+                (&palette.sunk, palette.sunk_glyph)
+            } else if self.hits.contains(&coordinates) {
+                (&palette.damaged, " X ")
+            } else {
+                (&palette.ship, palette.ship_glyph)
+            };
 
             write!(
                 stdout,
-                "{}{}   {}",
+                "{}{}{}{}",
                 Goto(screen_coords.x, screen_coords.y),
-                color::Bg(color::Red),
+                colour,
+                glyph,
                 style::Reset
             )
             .unwrap();
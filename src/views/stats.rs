@@ -0,0 +1,41 @@
+use crate::{
+    models::{Coordinates, Stats},
+    views::palette,
+};
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::style;
+
+// One faction's line in the results table.
+pub struct StatsView {
+    origin: Coordinates,
+    name: String,
+    model: Stats,
+}
+
+impl StatsView {
+    pub fn new(origin: Coordinates, name: String, model: Stats) -> StatsView {
+        StatsView {
+            origin,
+            name,
+            model,
+        }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        write!(
+            stdout,
+            "{}{}{:<14}Shots: {:<5}Accuracy: {:>3}%   Best streak: {:<4}Turns: {}{}",
+            Goto(self.origin.x, self.origin.y),
+            palette().text,
+            self.name,
+            self.model.shots,
+            self.model.accuracy(),
+            self.model.longest_streak,
+            self.model.turns,
+            style::Reset
+        )
+        .unwrap();
+    }
+}
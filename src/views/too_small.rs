@@ -0,0 +1,35 @@
+use std::io::{Stdout, Write};
+use termion::cursor::Goto;
+use termion::raw::RawTerminal;
+use termion::{clear, style};
+
+use crate::views::palette;
+
+// Shown in place of a screen that doesn't fit in the terminal.
+pub struct TooSmallView {
+    required: (u16, u16),
+    terminal: (u16, u16),
+}
+
+impl TooSmallView {
+    pub fn new(required: (u16, u16), terminal: (u16, u16)) -> TooSmallView {
+        TooSmallView { required, terminal }
+    }
+
+    pub fn render(&self, stdout: &mut RawTerminal<Stdout>) {
+        write!(
+            stdout,
+            "{}{}{}Terminal too small{}Needs {}x{}, has {}x{}. Make the window bigger to carry on.{}",
+            clear::All,
+            Goto(1, 1),
+            palette().warning,
+            Goto(1, 2),
+            self.required.0,
+            self.required.1,
+            self.terminal.0,
+            self.terminal.1,
+            style::Reset
+        )
+        .unwrap();
+    }
+}
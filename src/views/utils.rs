@@ -1,4 +1,4 @@
-use crate::models::Coordinates;
+use crate::models::{Board, Coordinates};
 
 pub fn translate_game_coords_to_board_coords(coordinates: Coordinates) -> Coordinates {
     Coordinates {
@@ -6,3 +6,25 @@ pub fn translate_game_coords_to_board_coords(coordinates: Coordinates) -> Coordi
         y: coordinates.y * 2 + 1,
     }
 }
+
+// The inverse of the above: which cell a position relative to a board's
+// origin falls in. Grid lines don't belong to any cell.
+pub fn translate_board_coords_to_game_coords(coordinates: Coordinates) -> Option<Coordinates> {
+    if coordinates.x.is_multiple_of(4) || coordinates.y.is_multiple_of(2) {
+        return None;
+    }
+    Some(Coordinates {
+        x: coordinates.x / 4,
+        y: coordinates.y / 2,
+    })
+}
+
+// The cell of `board`, drawn at `origin`, under a point on the screen.
+pub fn cell_at(origin: Coordinates, board: &Board, x: u16, y: u16) -> Option<Coordinates> {
+    let board_coords = Coordinates {
+        x: x.checked_sub(origin.x)?,
+        y: y.checked_sub(origin.y)?,
+    };
+    translate_board_coords_to_game_coords(board_coords)
+        .filter(|cell| cell.x < board.width && cell.y < board.height)
+}